@@ -1,12 +1,12 @@
-use std::{boxed::Box, error::Error, time::Duration};
+use std::{boxed::Box, error::Error, sync::Arc, time::Duration};
 
-use chrono::Local;
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeDelta, Timelike};
 use ratatui::{
     DefaultTerminal, Frame,
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
-    layout::Rect,
-    style::{Color, Stylize},
-    widgets::{Block, Paragraph},
+    crossterm::event::{self, Event, KeyEvent, MouseButton, MouseEvent, MouseEventKind},
+    layout::{Constraint, Layout, Position, Rect},
+    style::Stylize,
+    widgets::{Block, Paragraph, TableState, Wrap},
 };
 use tokio::{
     sync::mpsc::{Receiver, Sender},
@@ -14,41 +14,224 @@ use tokio::{
 };
 
 use crate::{
-    data::weather::{self, OpenMeteoResponse},
-    layout::{self, center},
+    config::Config,
+    data::{
+        alert_rules::{self, AlertSpan},
+        cap::CapAlert,
+        climate::{self, Climatology},
+        ensemble::EnsembleSpread,
+        location::Location,
+        marine::OpenMeteoMarine,
+        models::{ModelComparison, ModelVariable},
+        nowcast::{self, Nowcast},
+        weather::{self, OpenMeteoPollen, OpenMeteoResponse, WeatherQuery},
+    },
+    gradient::TemperatureGradient,
+    keymap::{Action, Context, Keymap},
+    layout::{self, Breakpoint, LayoutRow, Panel, center, popup},
+    notifier::{self, NotificationTracker, Notifier},
+    theme::Theme,
     weather_service::WeatherData,
     widgets::{
-        daily_weather::DailyWeather, loader::Loader, search::Search, weather_table::WeatherTable,
+        alerts::AlertList,
+        astronomy::AstronomyStrip,
+        big_conditions::BigConditions,
+        column_picker::ColumnPicker,
+        current_conditions::CurrentConditions,
+        daily_weather::{DailyWeather, DayView},
+        date_prompt::DatePrompt,
+        ensemble_chart::EnsembleChart,
+        help::Help,
+        hour_details::HourDetails,
+        loader::Loader,
+        model_table::ModelTable,
+        mountain_table::MountainTable,
+        nowcast::NowcastStrip,
+        pollen::PollenStrip,
+        search::Search,
+        tab_bar::{Tab, TabBar},
+        temperature_chart::TemperatureChart,
+        warning_banner::WarningBanner,
+        weather_table::WeatherTable,
     },
 };
 
+mod charts;
+mod days;
+mod details;
+mod history;
+mod hourly;
+mod marine;
+mod overview;
+
+/// What was drawn where in the last frame, for mouse clicks
+#[derive(Clone, Copy)]
+enum ClickArea {
+    Tab(Tab),
+    Search,
+    Day(usize),
+    Hourly,
+}
+
+/// What a tab shows and the keys it takes beyond the app-wide ones. Tabs
+/// share the selected day and hour, so the day and hour keys are handled once
+/// for all of them.
+trait Page {
+    fn draw(&self, app: &mut App, frame: &mut Frame, area: Rect, breakpoint: Breakpoint);
+
+    /// The tab's own actions, tried before the day and hour keys
+    fn handle_action(&self, _app: &mut App, _action: Action) -> bool {
+        false
+    }
+
+    /// Whether the hour keys move through the hourly table
+    fn moves_hours(&self) -> bool {
+        true
+    }
+}
+
+fn page(tab: Tab) -> &'static dyn Page {
+    match tab {
+        Tab::Overview => &overview::Overview,
+        Tab::Hourly => &hourly::Hourly,
+        Tab::Charts => &charts::Charts,
+        Tab::Days => &days::Days,
+        Tab::History => &history::History,
+        Tab::Details => &details::Details,
+        Tab::Marine => &marine::Marine,
+    }
+}
+
+/// Where typed keys go
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Search,
+    Content,
+}
+
 pub struct App {
+    config: Config,
+    theme: Theme,
+    gradient: TemperatureGradient,
+    /// Dashboard rows, from the config or the built-in layout
+    layout: Vec<LayoutRow>,
     search: Search,
     loader: Loader,
     daily: DailyWeather,
-    location_name: Option<String>,
+    hourly_state: TableState,
+    hourly_page: u16,
+    column_picker: ColumnPicker,
+    keymap: Keymap,
+    help: Help,
+    date_prompt: DatePrompt,
+    location: Option<Location>,
+    history: Option<(NaiveDate, NaiveDate)>,
+    climate: Option<Climatology>,
+    ensemble: Option<EnsembleSpread>,
+    show_ensemble: bool,
+    /// Shown in the status bar instead of the hints until the next action
+    notice: Option<&'static str>,
+    comparison: Option<ModelComparison>,
+    marine: Option<OpenMeteoMarine>,
+    show_mountain: bool,
+    /// Big current conditions instead of the dashboard
+    big: bool,
+    tab: Tab,
+    focus: Focus,
+    click_areas: Vec<(Rect, ClickArea)>,
+    /// Variable shown in the model comparison table, `None` hides it
+    compared: Option<ModelVariable>,
     exit: bool,
     weather: OpenMeteoResponse,
+    pollen: OpenMeteoPollen,
+    alerts: Vec<AlertSpan>,
+    warnings: Vec<CapAlert>,
+    notifier: Option<Arc<dyn Notifier>>,
+    announced: NotificationTracker,
     weather_tx: Sender<WeatherData>,
     loading: bool,
     refresh_handle: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl App {
-    pub fn new(weather_tx: Sender<WeatherData>) -> Self {
+    pub fn new(
+        weather_tx: Sender<WeatherData>,
+        config: Config,
+        theme: Theme,
+        gradient: TemperatureGradient,
+        keymap: Keymap,
+    ) -> Self {
         Self {
+            column_picker: ColumnPicker::new(config.hourly.columns.clone())
+                .theme(theme)
+                .hint(popup_hint(
+                    &keymap,
+                    &[
+                        (&[Action::ToggleColumn], "toggle"),
+                        (&[Action::ClosePopup], "close"),
+                    ],
+                )),
+            help: Help::new(help_sections(&keymap))
+                .theme(theme)
+                .hint(popup_hint(
+                    &keymap,
+                    &[
+                        (&[Action::PreviousHour, Action::NextHour], "scroll"),
+                        (&[Action::ClosePopup], "close"),
+                    ],
+                )),
+            daily: DailyWeather::default()
+                .theme(theme)
+                .gradient(gradient.clone())
+                .icons(config.icons),
+            big: config.big,
+            layout: if config.layout.is_empty() {
+                layout::default_rows()
+            } else {
+                config.layout.clone()
+            },
+            config,
             search: Search::default(),
-            daily: DailyWeather::default(),
-            location_name: None,
+            tab: Tab::Overview,
+            focus: Focus::Search,
+            click_areas: Vec::new(),
+
+            hourly_state: TableState::default(),
+            hourly_page: 0,
+            date_prompt: DatePrompt::default().theme(theme),
+            location: None,
+            history: None,
+            climate: None,
+            ensemble: None,
+            show_ensemble: false,
+            notice: None,
+            comparison: None,
+            marine: None,
+            show_mountain: false,
+            compared: None,
             exit: false,
             weather: OpenMeteoResponse::default(),
+            pollen: OpenMeteoPollen::default(),
+            alerts: Vec::new(),
+            warnings: Vec::new(),
+            notifier: None,
+            announced: NotificationTracker::default(),
             weather_tx,
             loading: false,
-            loader: Loader::default(),
+            loader: Loader::default().theme(theme),
+            theme,
+            gradient,
+            keymap,
             refresh_handle: None,
         }
     }
 
+    /// Where forecast notifications go, none are sent without one
+    pub fn notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
     pub async fn run(
         &mut self,
         terminal: &mut DefaultTerminal,
@@ -74,58 +257,498 @@ impl App {
 
     fn update_state(&mut self, weather_data: WeatherData) {
         self.daily.data(weather_data.weather.daily.clone());
+        self.alerts = alert_rules::evaluate(
+            &self.config.alerts,
+            &weather::hourly_weather(&weather_data.weather),
+        );
+        self.daily.alerts(self.alerts.clone());
+        if weather_data.history.is_none() {
+            self.notify(&weather::hourly_weather(&weather_data.weather));
+        }
         self.weather = weather_data.weather;
-        self.location_name = Some(weather_data.location_name.clone());
+        self.pollen = weather_data.pollen;
+        self.warnings = weather_data.warnings;
+        if weather_data.climate.is_some() {
+            self.climate = weather_data.climate;
+        } else if self.location.as_ref().map(|location| location.coordinates)
+            != Some(weather_data.location.coordinates)
+        {
+            self.climate = None;
+        }
+        self.daily.climate(
+            self.climate
+                .as_ref()
+                .map(|climate| climate.compare_days(&self.weather.daily))
+                .unwrap_or_default(),
+        );
+        self.daily.spread(
+            weather_data
+                .ensemble
+                .as_ref()
+                .map(|ensemble| ensemble.daily_high_ranges())
+                .unwrap_or_default(),
+        );
+        self.ensemble = weather_data.ensemble;
+        self.comparison = weather_data.comparison;
+        self.marine = weather_data.marine;
+        if self.marine.is_none() && self.tab == Tab::Marine {
+            self.tab = Tab::Overview;
+        }
+        self.location = Some(weather_data.location);
+        if self.hourly_state.selected().is_none() || weather_data.history != self.history {
+            self.select_current_hour();
+        }
+        if weather_data.history.is_some() {
+            self.tab = Tab::History;
+        }
+        self.history = weather_data.history;
         self.loading = false;
-        self.loader = Loader::default();
+        self.loader = Loader::default().theme(self.theme);
+    }
+
+    /// Rain in the next hours, only for the forecast. Worked out again on
+    /// every draw so the minutes count down between refreshes.
+    fn nowcast(&self) -> Option<Nowcast> {
+        if self.history.is_some() {
+            return None;
+        }
+        nowcast::nowcast(&self.weather.minutely_15, Local::now().naive_local())
+    }
+
+    fn notify(&mut self, hourly: &[weather::Weather]) {
+        let Some(notifier) = self.notifier.clone() else {
+            return;
+        };
+
+        let now = Local::now().naive_local();
+        let events =
+            notifier::upcoming_events(&self.config.notifications, hourly, &self.alerts, now);
+        for event in self.announced.unannounced(events, now) {
+            let notifier = notifier.clone();
+            // D-Bus calls block, keep them off the render loop
+            tokio::task::spawn_blocking(move || {
+                let _ = notifier.notify(&event.summary, &event.body);
+            });
+        }
+    }
+
+    fn select_current_hour(&mut self) {
+        let now = Local::now().naive_local();
+        let hour = if self.daily.clone().selected() == now.date() {
+            now.hour() as usize
+        } else {
+            0
+        };
+        self.hourly_state.select(Some(hour));
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        self.click_areas.clear();
+        let breakpoint = Breakpoint::for_area(frame.area());
+        if breakpoint == Breakpoint::TooSmall {
+            let message = format!(
+                "Terminal too small: {}×{}, needs at least {}×{}",
+                frame.area().width,
+                frame.area().height,
+                Breakpoint::MIN_WIDTH,
+                Breakpoint::MIN_HEIGHT
+            );
+            frame.render_widget(
+                Paragraph::new(message).centered().wrap(Wrap { trim: true }),
+                popup(frame.area(), frame.area().width, 2),
+            );
+            return;
+        }
+
+        if self.big {
+            self.draw_big(frame);
+            return;
+        }
+
+        self.daily.view(match breakpoint {
+            Breakpoint::Wide => DayView::Cards,
+            Breakpoint::Narrow => DayView::List,
+            _ => DayView::Single,
+        });
+        let selected_day = self.daily.clone().selected();
+        let hours = weather::hourly_weather_for(&self.weather, selected_day).len();
+        if let Some(index) = self.hourly_state.selected()
+            && index >= hours
+        {
+            self.hourly_state.select(Some(hours.saturating_sub(1)));
+        }
+
+        let [tabs_area, body_area] = layout::tabbed_layout(frame.area());
+        self.click_areas.extend(
+            TabBar::tab_areas(&self.tabs(), tabs_area)
+                .into_iter()
+                .map(|(tab, tab_area)| (tab_area, ClickArea::Tab(tab))),
+        );
+        frame.render_widget(
+            TabBar::new(self.tabs(), self.tab).theme(self.theme),
+            tabs_area,
+        );
+        self.search.focused(self.focus == Focus::Search);
+        if self.tab == Tab::Overview {
+            page(self.tab).draw(self, frame, body_area, breakpoint);
+        } else {
+            let [search_area, page_area, status_area] = layout::page_layout(body_area);
+            let page_area = center(page_area, breakpoint.content_width(page_area));
+            self.draw_search(frame, search_area);
+            page(self.tab).draw(self, frame, page_area, breakpoint);
+            self.draw_status(frame, status_area);
+        }
+
+        if self.column_picker.is_open() {
+            frame.render_widget(self.column_picker.clone(), popup(frame.area(), 30, 15));
+        }
+
+        if self.date_prompt.is_open() {
+            frame.render_widget(self.date_prompt.clone(), popup(frame.area(), 50, 3));
+        }
+
+        if self.help.is_open() {
+            frame.render_widget(
+                self.help.clone(),
+                popup(frame.area(), 70, self.help.height()),
+            );
+        }
+    }
+
+    fn draw_panel(&mut self, frame: &mut Frame, panel: Panel, area: Rect) {
+        let selected_day = self.daily.clone().selected();
+        let has_hourly = !self.weather.hourly.date_time.is_empty();
+        match panel {
+            Panel::Search => self.draw_search(frame, area),
+            Panel::Title => {
+                if let Some(location) = &self.location {
+                    let location_name = &location.name;
+                    let title = match self.history {
+                        Some((start, end)) if start == end => {
+                            format!("{} · History {}", location_name, start.format("%b %-d, %Y"))
+                        }
+                        Some((start, end)) => format!(
+                            "{} · History {} – {}",
+                            location_name,
+                            start.format("%b %-d"),
+                            end.format("%b %-d, %Y")
+                        ),
+                        None => location_name.clone(),
+                    };
+                    let title = format!(
+                        "{} · {:.0} ft",
+                        title,
+                        weather::meters_to_feet(self.weather.elevation)
+                    );
+                    frame.render_widget(Paragraph::new(title).bold().centered(), area);
+                }
+            }
+            Panel::Now => {
+                if self.location.is_some() && self.history.is_none() {
+                    frame.render_widget(self.current_conditions(), area);
+                }
+            }
+            Panel::Warnings => frame.render_widget(
+                WarningBanner::new(self.warnings.clone())
+                    .theme(self.theme)
+                    .icons(self.config.icons),
+                area,
+            ),
+            Panel::Daily => {
+                if !self.weather.daily.date.is_empty() {
+                    self.draw_daily(frame, self.daily.clone(), area);
+                }
+            }
+            Panel::AirQuality if weather::has_pollen(&self.pollen) => frame.render_widget(
+                PollenStrip::new(weather::daily_pollen_for(&self.pollen, selected_day))
+                    .theme(self.theme)
+                    .icons(self.config.icons),
+                area,
+            ),
+            Panel::Nowcast => frame.render_widget(
+                NowcastStrip::new(self.nowcast())
+                    .theme(self.theme)
+                    .icons(self.config.icons),
+                area,
+            ),
+            Panel::Hourly if has_hourly => self.draw_hourly(frame, area),
+            Panel::Details if has_hourly => {
+                let selected = self.hourly_state.selected().and_then(|index| {
+                    weather::hourly_weather_for(&self.weather, selected_day)
+                        .get(index)
+                        .cloned()
+                });
+                let earlier = selected.as_ref().and_then(|selected| {
+                    weather::weather_lookup(
+                        &self.weather,
+                        WeatherQuery::Hourly {
+                            date_time: selected.date_time - TimeDelta::hours(3),
+                        },
+                    )
+                });
+                frame.render_widget(
+                    HourDetails::new(selected, earlier)
+                        .theme(self.theme)
+                        .icons(self.config.icons),
+                    area,
+                );
+            }
+            Panel::Alerts if has_hourly && !self.config.alerts.is_empty() => frame.render_widget(
+                AlertList::new(self.alerts.clone())
+                    .theme(self.theme)
+                    .icons(self.config.icons),
+                area,
+            ),
+            Panel::Chart => frame.render_widget(
+                TemperatureChart::new(weather::hourly_weather_for(&self.weather, selected_day))
+                    .theme(self.theme),
+                area,
+            ),
+            Panel::Astronomy => frame.render_widget(
+                AstronomyStrip::new(weather::astronomy_for(&self.weather.daily, selected_day))
+                    .theme(self.theme),
+                area,
+            ),
+            Panel::Status => self.draw_status(frame, area),
+            Panel::Hourly | Panel::Details | Panel::Alerts | Panel::AirQuality => {}
+        }
+    }
+
+    /// The hourly table, or the view that replaces it
+    fn draw_hourly(&mut self, frame: &mut Frame, area: Rect) {
+        self.click_areas.push((area, ClickArea::Hourly));
+        let selected_day = self.daily.clone().selected();
+        let hourly = weather::hourly_weather_for(&self.weather, selected_day);
+
+        // Title and header rows aren't scrolled
+        self.hourly_page = area.height.saturating_sub(2);
+        let columns = self
+            .column_picker
+            .columns()
+            .iter()
+            .map(|&column| (column, self.config.hourly.constraint(column)))
+            .collect();
+        match (&self.comparison, self.compared, &self.ensemble) {
+            // The archive has no pressure levels, history falls through
+            _ if self.show_mountain
+                && self
+                    .weather
+                    .hourly
+                    .freezing_level_height
+                    .iter()
+                    .any(Option::is_some) =>
+            {
+                frame.render_widget(
+                    MountainTable::new(
+                        weather::mountain_for(&self.weather, selected_day),
+                        self.weather.elevation,
+                    )
+                    .theme(self.theme),
+                    area,
+                )
+            }
+            (Some(comparison), Some(variable), _) => frame.render_widget(
+                ModelTable::new(
+                    comparison.models.clone(),
+                    variable,
+                    comparison.day(variable, selected_day),
+                )
+                .next_key(self.keymap.key(Action::CycleComparison))
+                .theme(self.theme),
+                area,
+            ),
+            (.., Some(ensemble)) if self.show_ensemble => frame.render_widget(
+                EnsembleChart::new(ensemble.clone())
+                    .theme(self.theme)
+                    .icons(self.config.icons),
+                area,
+            ),
+            _ => frame.render_stateful_widget(
+                WeatherTable::new(hourly, columns)
+                    .alerts(self.alerts.clone())
+                    .theme(self.theme)
+                    .gradient(self.gradient.clone())
+                    .icons(self.config.icons),
+                area,
+                &mut self.hourly_state,
+            ),
+        }
+    }
+
+    /// Days drawn anywhere can be clicked to select them
+    fn draw_daily(&mut self, frame: &mut Frame, daily: DailyWeather, area: Rect) {
+        self.click_areas.extend(
+            daily
+                .day_areas(area)
+                .into_iter()
+                .map(|(index, day_area)| (day_area, ClickArea::Day(index))),
+        );
+        frame.render_widget(daily, area);
+    }
+
+    fn draw_big(&mut self, frame: &mut Frame) {
+        let [search_area, big_area, status_area] = layout::page_layout(frame.area());
+        self.draw_search(frame, search_area);
+
+        if let Some(location) = &self.location
+            && self.history.is_none()
+        {
+            let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+            let daily = &self.weather.daily;
+            let high_low = daily
+                .date
+                .iter()
+                .position(|date| *date == today)
+                .and_then(|i| {
+                    Some((
+                        weather::at(&daily.temperature_2m_max, i)?,
+                        weather::at(&daily.temperature_2m_min, i)?,
+                    ))
+                });
+            frame.render_widget(
+                BigConditions::new(location.name.clone(), self.weather.current.clone())
+                    .today(high_low)
+                    .nowcast(self.nowcast())
+                    .theme(self.theme)
+                    .icons(self.config.icons)
+                    .gradient(self.gradient.clone()),
+                big_area,
+            );
+        }
+
+        self.draw_status(frame, status_area);
     }
 
-    fn draw(&self, frame: &mut Frame) {
-        let app_layout = layout::default_layout(frame.area());
-        let centered_search = center(app_layout[0], app_layout[0].width / 3);
-        let centered_title = center(app_layout[1], (app_layout[1].width as f32 * 0.8) as u16);
-        let centered_daily = center(app_layout[2], (app_layout[2].width as f32 * 0.8) as u16);
-        let centered_weather = center(app_layout[3], (app_layout[3].width as f32 * 0.8) as u16);
-        let status_line = center(app_layout[4], (app_layout[4].width as f32 * 0.8) as u16);
+    fn draw_search(&mut self, frame: &mut Frame, area: Rect) {
+        // A third of a full row, but never too narrow to type in
+        let centered_search = center(area, (area.width / 3).max(30).min(area.width));
         let loader_area = Rect {
             x: centered_search.x + centered_search.width.saturating_sub(3),
             y: centered_search.y + centered_search.height.saturating_sub(2),
             width: 1,
             height: 1,
         };
+        self.click_areas.push((centered_search, ClickArea::Search));
         frame.render_widget(self.search.clone(), centered_search);
         if self.loading {
             frame.render_widget(self.loader.clone(), loader_area);
         }
+    }
 
-        if let Some(location_name) = &self.location_name {
-            let title = Paragraph::new(location_name.as_str()).bold().centered();
-            frame.render_widget(title, centered_title);
-        }
+    fn draw_status(&self, frame: &mut Frame, area: Rect) {
+        let status_line = center(area, Breakpoint::for_area(frame.area()).content_width(area));
+        let status_line_block = Block::new()
+            .bg(self.theme.status_bg)
+            .fg(self.theme.status_fg);
+        frame.render_widget(status_line_block, area);
+        let [hint_area, time_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(8)])
+                .spacing(1)
+                .areas(status_line);
+        let time = Local::now().format("%H:%M:%S").to_string();
+        frame.render_widget(Paragraph::new(time).right_aligned().bold(), time_area);
 
-        if self.weather.hourly.date_time.len() > 0 {
-            frame.render_widget(
-                WeatherTable::new(weather::hourly_weather_for(
-                    &self.weather,
-                    self.daily.clone().selected(),
-                )),
-                centered_weather,
-            );
-        }
+        let status = match self.notice {
+            Some(notice) => notice.to_string(),
+            None => self.hints().join(" · "),
+        };
+        frame.render_widget(Paragraph::new(status), hint_area);
+    }
+
+    /// The keys most useful where the focus is, for the status bar
+    fn hints(&self) -> Vec<String> {
+        let actions = if self.focus == Focus::Search {
+            vec![
+                (Action::Search, "search"),
+                (Action::LeaveSearch, "leave search"),
+                (Action::ClearSearch, "clear"),
+                (Action::Help, "help"),
+            ]
+        } else {
+            let mut actions = match self.tab {
+                Tab::Overview | Tab::Details => {
+                    vec![
+                        (Action::NextDay, "next day"),
+                        (Action::NextHour, "next hour"),
+                    ]
+                }
+                Tab::Hourly => vec![
+                    (Action::NextDay, "next day"),
+                    (Action::NextHour, "next hour"),
+                    (Action::Columns, "columns"),
+                ],
+                Tab::Charts if self.ensemble.is_some() => vec![
+                    (Action::NextDay, "next day"),
+                    (Action::ToggleEnsemble, "ensemble"),
+                ],
+                Tab::Charts => vec![(Action::NextDay, "next day")],
+                Tab::Days => vec![(Action::NextHour, "next day"), (Action::Select, "hours")],
+                Tab::Marine => vec![(Action::NextDay, "next day")],
+                Tab::History => vec![
+                    (Action::Select, "pick dates"),
+                    (Action::NextDay, "next day"),
+                    (Action::NextHour, "next hour"),
+                ],
+            };
+            actions.push((Action::FocusSearch, "search"));
+            actions
+        };
 
-        if self.weather.daily.date.len() > 0 {
-            frame.render_widget(self.daily.clone(), centered_daily);
+        let mut hints = actions
+            .into_iter()
+            .filter_map(|(action, hint)| Some(format!("{} {}", self.keymap.key(action)?, hint)))
+            .collect::<Vec<_>>();
+        if self.focus == Focus::Content {
+            let last_tab = if self.marine.is_some() {
+                Action::Tab7
+            } else {
+                Action::Tab6
+            };
+            if let (Some(first), Some(last)) =
+                (self.keymap.key(Action::Tab1), self.keymap.key(last_tab))
+            {
+                hints.push(format!("{}-{} tabs", first, last));
+            }
+            if let Some(help) = self.keymap.key(Action::Help) {
+                hints.push(format!("{} help", help));
+            }
         }
-        let status_line_block = Block::new().bg(Color::DarkGray).fg(Color::White);
-        frame.render_widget(status_line_block, app_layout[4]);
-        let time = Local::now().format("%H:%M:%S").to_string();
-        frame.render_widget(Paragraph::new(time).right_aligned().bold(), status_line);
+        hints
+    }
+
+    /// Current conditions compared against the same hour and the daily high
+    /// yesterday, available when the forecast includes past days
+    fn current_conditions(&self) -> CurrentConditions {
+        let current = self.weather.current.clone();
+        let now = current.as_ref().and_then(|current| {
+            NaiveDateTime::parse_from_str(&current.date_time, "%Y-%m-%dT%H:%M").ok()
+        });
+        let yesterday = now.and_then(|now| {
+            let hour = now.date().and_hms_opt(now.hour(), 0, 0)?;
+            weather::weather_lookup(
+                &self.weather,
+                WeatherQuery::Hourly {
+                    date_time: hour - TimeDelta::days(1),
+                },
+            )
+        });
+        let high = |date: NaiveDate| {
+            weather::weather_lookup(&self.weather, WeatherQuery::Daily { date })
+                .and_then(|weather| weather.temp)
+        };
+        let high_delta =
+            now.and_then(|now| Some(high(now.date())? - high(now.date() - TimeDelta::days(1))?));
+
+        CurrentConditions::new(current, yesterday, high_delta)
+            .theme(self.theme)
+            .icons(self.config.icons)
     }
 
     async fn handle_events(&mut self) -> Result<(), Box<dyn Error>> {
         if event::poll(Duration::from_millis(10))? {
             match event::read()? {
                 Event::Key(key_event) => self.handle_key_event(key_event).await,
+                Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
                 _ => {}
             }
         }
@@ -133,51 +756,309 @@ impl App {
         Ok(())
     }
 
+    /// Clicks select whatever is under the mouse, scrolling moves through
+    /// days or hours
+    fn handle_mouse_event(&mut self, mouse_event: MouseEvent) {
+        if self.column_picker.is_open() || self.date_prompt.is_open() || self.help.is_open() {
+            return;
+        }
+
+        let position = Position::new(mouse_event.column, mouse_event.row);
+        let target = self
+            .click_areas
+            .iter()
+            .find(|(area, _)| area.contains(position))
+            .map(|&(_, target)| target);
+        match (mouse_event.kind, target) {
+            (MouseEventKind::Down(MouseButton::Left), target) => {
+                self.focus = Focus::Content;
+                match target {
+                    Some(ClickArea::Tab(tab)) => self.select_tab(tab),
+                    Some(ClickArea::Search) => self.focus = Focus::Search,
+                    Some(ClickArea::Day(index)) => self.daily.select(index),
+                    Some(ClickArea::Hourly) | None => {}
+                }
+            }
+            (MouseEventKind::ScrollDown, Some(ClickArea::Hourly)) => {
+                self.hourly_state.select_next()
+            }
+            (MouseEventKind::ScrollUp, Some(ClickArea::Hourly)) => {
+                self.hourly_state.select_previous()
+            }
+            (MouseEventKind::ScrollDown, Some(ClickArea::Day(_))) => self.daily.select_next(),
+            (MouseEventKind::ScrollUp, Some(ClickArea::Day(_))) => self.daily.select_previous(),
+            _ => {}
+        }
+    }
+
     async fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match key_event {
-            KeyEvent {
-                modifiers: KeyModifiers::CONTROL,
-                code: KeyCode::Char('c'),
-                ..
-            } => self.exit = true,
-            KeyEvent {
-                code: KeyCode::Enter,
-                ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('r'),
-                modifiers: KeyModifiers::CONTROL,
-                ..
-            } => {
+        if self.column_picker.is_open() || self.help.is_open() || self.date_prompt.is_open() {
+            if self.date_prompt.is_open() && self.keymap.is_text_key(key_event) {
+                self.date_prompt.handle_key_event(key_event);
+                return;
+            }
+            match self.keymap.action(key_event, Context::Popup) {
+                Some(action) => self.handle_popup_action(action),
+                // Editing shortcuts of the date box itself
+                None if self.date_prompt.is_open() => self.date_prompt.handle_key_event(key_event),
+                None => {}
+            }
+            return;
+        }
+
+        let searching = self.focus == Focus::Search;
+        if searching && self.keymap.is_text_key(key_event) {
+            self.search.handle_key_event(key_event);
+            return;
+        }
+        let context = if searching {
+            Context::Search
+        } else {
+            Context::Forecast
+        };
+        match self.keymap.action(key_event, context) {
+            Some(action) => self.handle_action(action),
+            // Editing shortcuts of the search box itself
+            None if searching => self.search.handle_key_event(key_event),
+            None => {}
+        }
+    }
+
+    /// The open popup gets every action but quitting
+    fn handle_popup_action(&mut self, action: Action) {
+        if action == Action::Quit {
+            self.exit = true;
+        } else if self.column_picker.is_open() {
+            self.column_picker.handle_action(action);
+        } else if self.help.is_open() {
+            self.help.handle_action(action);
+        } else if let Some(range) = self.date_prompt.handle_action(action) {
+            if let Some(refresh_handle) = self.refresh_handle.take() {
+                refresh_handle.abort();
+            }
+            self.refresh_handle = self.update_history(range);
+        }
+    }
+
+    fn handle_action(&mut self, action: Action) {
+        self.notice = None;
+        match action {
+            Action::Quit => self.exit = true,
+            Action::Refresh => self.refresh(),
+            Action::Help => self.help.toggle_open(),
+            Action::Columns => self.column_picker.toggle_open(),
+            Action::History => self.open_date_prompt(),
+            Action::ToggleBig => self.big = !self.big,
+            Action::ToggleEnsemble if self.ensemble.is_some() => {
+                self.show_ensemble = !self.show_ensemble
+            }
+            Action::ToggleEnsemble => {
+                self.notice = Some(if self.config.ensemble.enabled {
+                    "No ensemble forecast for this location"
+                } else {
+                    "The ensemble spread is off, enable it under [ensemble] in the config"
+                })
+            }
+            Action::ToggleMountain => self.show_mountain = !self.show_mountain,
+            Action::CycleComparison => {
+                self.compared = match self.compared {
+                    None => Some(ModelVariable::Temp),
+                    Some(variable) => variable.next(),
+                }
+            }
+            Action::Tab1 => self.select_tab(Tab::Overview),
+            Action::Tab2 => self.select_tab(Tab::Hourly),
+            Action::Tab3 => self.select_tab(Tab::Charts),
+            Action::Tab4 => self.select_tab(Tab::Days),
+            Action::Tab5 => self.select_tab(Tab::History),
+            Action::Tab6 => self.select_tab(Tab::Details),
+            Action::Tab7 if self.marine.is_some() => self.select_tab(Tab::Marine),
+            Action::Tab7 => self.notice = Some("No marine forecast for this location"),
+            Action::FocusSearch => self.focus = Focus::Search,
+            Action::LeaveSearch => self.focus = Focus::Content,
+            Action::ClearSearch => self.search.clear_text(),
+            Action::Search => {
                 if let Some(refresh_handle) = self.refresh_handle.take() {
                     refresh_handle.abort();
                 }
+                self.hourly_state.select(None);
+                self.announced.reset();
+                self.refresh_handle = Some(self.update_weather(None));
+                self.focus = Focus::Content;
+            }
+            _ => {
+                if !self.handle_tab_action(action) {
+                    self.notice = Some("Nothing to do for that key on this tab");
+                }
+            }
+        }
+    }
+
+    /// Each tab handles the actions it uses, returning whether it did
+    fn handle_tab_action(&mut self, action: Action) -> bool {
+        let page = page(self.tab);
+        page.handle_action(self, action)
+            || self.handle_day_action(action)
+            || (page.moves_hours() && self.handle_hour_action(action))
+    }
+
+    fn refresh(&mut self) {
+        if let Some(refresh_handle) = self.refresh_handle.take() {
+            refresh_handle.abort();
+        }
+        self.refresh_handle = Some(self.update_weather(None));
+    }
+
+    fn open_date_prompt(&mut self) {
+        if self.location.is_some() {
+            self.date_prompt.open();
+        }
+    }
+
+    fn tabs(&self) -> Vec<Tab> {
+        Tab::available(self.marine.is_some())
+    }
 
-                self.refresh_handle = Some(self.update_weather());
+    /// Leaving the history tab goes back to the forecast for the same place,
+    /// whatever has been typed into the search bar since
+    fn select_tab(&mut self, tab: Tab) {
+        if self.tab == Tab::History && tab != Tab::History && self.history.is_some() {
+            if let Some(refresh_handle) = self.refresh_handle.take() {
+                refresh_handle.abort();
             }
-            KeyEvent {
-                code: KeyCode::Tab, ..
-            } => self.daily.select_next(),
-            KeyEvent {
-                code: KeyCode::BackTab,
-                ..
-            } => self.daily.select_previous(),
-            _ => self.search.handle_key_event(key_event),
+            self.refresh_handle = Some(self.update_weather(self.location.clone()));
+        }
+        self.tab = tab;
+    }
+
+    fn handle_day_action(&mut self, action: Action) -> bool {
+        match action {
+            Action::NextDay => self.daily.select_next(),
+            Action::PreviousDay => self.daily.select_previous(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_hour_action(&mut self, action: Action) -> bool {
+        match action {
+            Action::NextHour => self.hourly_state.select_next(),
+            Action::PreviousHour => self.hourly_state.select_previous(),
+            Action::PageDown => self.hourly_state.scroll_down_by(self.hourly_page.max(1)),
+            Action::PageUp => self.hourly_state.scroll_up_by(self.hourly_page.max(1)),
+            _ => return false,
         }
+        true
     }
 
-    fn update_weather(&mut self) -> tokio::task::JoinHandle<()> {
+    /// Geocodes the search bar text unless the location is already known
+    fn update_weather(&mut self, location: Option<Location>) -> tokio::task::JoinHandle<()> {
         self.loading = true;
         let tx = self.weather_tx.clone();
         let query = self.search.text().to_string();
+        let config = self.config.clone();
+        let archive_url = self.config.history.archive_url.clone();
 
         tokio::spawn(async move {
+            let mut climate: Option<Climatology> = None;
             loop {
-                if let Ok(result) = crate::weather_service::dispatch_weather(query.as_str()).await {
-                    let _ = tx.send(result).await;
+                let result = match location.clone() {
+                    Some(location) => {
+                        crate::weather_service::dispatch_forecast(location, &config).await
+                    }
+                    None => crate::weather_service::dispatch_weather(query.as_str(), &config).await,
+                };
+                if let Ok(mut result) = result {
+                    if climate.is_none() {
+                        // Show the forecast first, normals can take a while the first time
+                        let _ = tx.send(result.clone()).await;
+                        let (lat, lon) = result.location.coordinates;
+                        climate = climate::fetch_climatology(&archive_url, lat, lon)
+                            .await
+                            .ok();
+                    }
+                    if climate.is_some() {
+                        result.climate = climate.clone();
+                        let _ = tx.send(result).await;
+                    }
                 }
                 tokio::time::sleep(Duration::from_secs(1200)).await;
             }
         })
     }
+
+    /// Past weather doesn't change, so unlike the forecast it's fetched once
+    fn update_history(
+        &mut self,
+        range: (NaiveDate, NaiveDate),
+    ) -> Option<tokio::task::JoinHandle<()>> {
+        let location = self.location.clone()?;
+        let archive_url = self.config.history.archive_url.clone();
+        let tx = self.weather_tx.clone();
+        self.loading = true;
+
+        Some(tokio::spawn(async move {
+            if let Ok(result) =
+                crate::weather_service::dispatch_history(location, range, &archive_url).await
+            {
+                let _ = tx.send(result).await;
+            }
+        }))
+    }
+}
+
+/// A popup's keys for its bottom border, e.g. ` Up/Down scroll · Esc close `.
+/// Hints for actions without a key are left out.
+fn popup_hint(keymap: &Keymap, hints: &[(&[Action], &str)]) -> String {
+    let hints: Vec<String> = hints
+        .iter()
+        .filter_map(|(actions, what)| {
+            let keys = actions
+                .iter()
+                .map(|&action| keymap.key(action))
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("{} {}", keys.join("/"), what))
+        })
+        .collect();
+    format!(" {} ", hints.join(" · "))
+}
+
+/// Every key binding for the help popup, grouped by where it applies
+fn help_sections(keymap: &Keymap) -> Vec<(String, Vec<(String, String)>)> {
+    let section = |title: &str, context: Context| {
+        let bindings = Action::ALL
+            .iter()
+            .filter(|action| action.context() == context)
+            .filter(|&&action| !keymap.keys(action).is_empty())
+            .map(|&action| {
+                let keys = keymap
+                    .keys(action)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (keys, action.description().to_string())
+            })
+            .collect();
+        (title.to_string(), bindings)
+    };
+    vec![
+        section("Anywhere", Context::Anywhere),
+        section("Search", Context::Search),
+        section("Forecast", Context::Forecast),
+        section("Popups", Context::Popup),
+        (
+            "Mouse".to_string(),
+            vec![
+                (
+                    "Click".to_string(),
+                    "Select a tab or day, or focus the search bar".to_string(),
+                ),
+                (
+                    "Scroll".to_string(),
+                    "Move through hours or days".to_string(),
+                ),
+            ],
+        ),
+    ]
 }
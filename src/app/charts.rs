@@ -0,0 +1,43 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+};
+
+use super::{App, Page};
+use crate::{
+    layout::{Breakpoint, Panel},
+    widgets::{daily_weather::DayView, ensemble_chart::EnsembleChart},
+};
+
+/// The selected day's temperatures, over the ensemble spread when there
+/// is one
+pub(super) struct Charts;
+
+impl Page for Charts {
+    fn draw(&self, app: &mut App, frame: &mut Frame, area: Rect, _breakpoint: Breakpoint) {
+        let ensemble_height = if app.ensemble.is_some() { 1 } else { 0 };
+        let [day_area, chart_area, ensemble_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Fill(1),
+            Constraint::Fill(ensemble_height),
+        ])
+        .areas(area);
+
+        let mut day = app.daily.clone();
+        day.view(DayView::Single);
+        app.draw_daily(frame, day, day_area);
+        app.draw_panel(frame, Panel::Chart, chart_area);
+        if let Some(ensemble) = &app.ensemble {
+            frame.render_widget(
+                EnsembleChart::new(ensemble.clone())
+                    .theme(app.theme)
+                    .icons(app.config.icons),
+                ensemble_area,
+            );
+        }
+    }
+
+    fn moves_hours(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,38 @@
+use ratatui::{Frame, layout::Rect};
+
+use super::{App, Page};
+use crate::{
+    keymap::Action,
+    layout::Breakpoint,
+    widgets::{daily_weather::DayView, tab_bar::Tab},
+};
+
+/// Every forecast day as a list
+pub(super) struct Days;
+
+impl Page for Days {
+    fn draw(&self, app: &mut App, frame: &mut Frame, area: Rect, _breakpoint: Breakpoint) {
+        if app.weather.daily.date.is_empty() {
+            return;
+        }
+        let mut days = app.daily.clone();
+        days.view(DayView::List);
+        days.visible(None);
+        app.draw_daily(frame, days, area);
+    }
+
+    /// Hour keys move between days too, selecting opens the day's hours
+    fn handle_action(&self, app: &mut App, action: Action) -> bool {
+        match action {
+            Action::NextHour => app.daily.select_next(),
+            Action::PreviousHour => app.daily.select_previous(),
+            Action::Select => app.select_tab(Tab::Hourly),
+            _ => return false,
+        }
+        true
+    }
+
+    fn moves_hours(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,43 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+};
+
+use super::{App, Page};
+use crate::layout::{Breakpoint, Panel};
+
+/// The selected hour in full next to everything else known about the
+/// selected day
+pub(super) struct Details;
+
+impl Page for Details {
+    fn draw(&self, app: &mut App, frame: &mut Frame, area: Rect, breakpoint: Breakpoint) {
+        let [hour_area, day_area] = if breakpoint == Breakpoint::Wide {
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)])
+                .spacing(2)
+                .areas(area)
+        } else {
+            Layout::vertical([Constraint::Length(12), Constraint::Fill(1)]).areas(area)
+        };
+
+        let [details_area, alerts_area] =
+            Layout::vertical([Constraint::Length(12), Constraint::Fill(1)]).areas(hour_area);
+        app.draw_panel(frame, Panel::Details, details_area);
+        app.draw_panel(frame, Panel::Alerts, alerts_area);
+
+        let panels = [
+            (Panel::Daily, 1),
+            (Panel::Now, 2),
+            (Panel::Astronomy, 1),
+            (Panel::AirQuality, 1),
+            (Panel::Nowcast, 1),
+            (Panel::Warnings, 1),
+        ];
+        let areas = Layout::vertical(panels.map(|(_, height)| Constraint::Length(height)))
+            .spacing(1)
+            .split(day_area);
+        for ((panel, _), &area) in panels.iter().zip(areas.iter()) {
+            app.draw_panel(frame, *panel, area);
+        }
+    }
+}
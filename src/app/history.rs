@@ -0,0 +1,71 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    widgets::Paragraph,
+};
+
+use super::{App, Page};
+use crate::{
+    keymap::Action,
+    layout::{Breakpoint, Panel},
+    widgets::daily_weather::DayView,
+};
+
+/// Past weather looked up with the date prompt, or how to look it up
+pub(super) struct History;
+
+impl Page for History {
+    fn draw(&self, app: &mut App, frame: &mut Frame, area: Rect, _breakpoint: Breakpoint) {
+        if app.history.is_none() {
+            let keys: Vec<String> = [Action::Select, Action::History]
+                .into_iter()
+                .filter_map(|action| app.keymap.key(action))
+                .collect();
+            let hint = match &app.location {
+                Some(location) if !keys.is_empty() => format!(
+                    "Press {} to look up past weather for {}",
+                    keys.join(" or "),
+                    location.name
+                ),
+                Some(location) => format!(
+                    "Bind a key to `history` to look up past weather for {}",
+                    location.name
+                ),
+                None => "Search for a location to look up its past weather".to_string(),
+            };
+            let [hint_area] = Layout::vertical([Constraint::Length(1)])
+                .flex(ratatui::layout::Flex::Center)
+                .areas(area);
+            frame.render_widget(
+                Paragraph::new(hint)
+                    .centered()
+                    .style(Style::new().fg(app.theme.muted))
+                    .italic(),
+                hint_area,
+            );
+            return;
+        }
+
+        let mut days = app.daily.clone();
+        days.view(DayView::List);
+        days.visible(None);
+        let [title_area, days_area, hourly_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(days.height()),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        app.draw_panel(frame, Panel::Title, title_area);
+        app.draw_daily(frame, days, days_area);
+        app.draw_panel(frame, Panel::Hourly, hourly_area);
+    }
+
+    fn handle_action(&self, app: &mut App, action: Action) -> bool {
+        match action {
+            Action::Select => app.open_date_prompt(),
+            _ => return false,
+        }
+        true
+    }
+}
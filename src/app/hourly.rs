@@ -0,0 +1,38 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+};
+
+use super::{App, Page};
+use crate::{
+    layout::{Breakpoint, Panel},
+    widgets::daily_weather::DayView,
+};
+
+/// The hourly table over the full height, details beside it when they fit
+pub(super) struct Hourly;
+
+impl Page for Hourly {
+    fn draw(&self, app: &mut App, frame: &mut Frame, area: Rect, breakpoint: Breakpoint) {
+        let [day_area, area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+        let mut day = app.daily.clone();
+        day.view(DayView::Single);
+        app.draw_daily(frame, day, day_area);
+
+        let side_width = if breakpoint == Breakpoint::Wide {
+            32
+        } else {
+            0
+        };
+        let [table_area, side_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(side_width)])
+                .spacing(1)
+                .areas(area);
+        let [details_area, alerts_area] =
+            Layout::vertical([Constraint::Length(12), Constraint::Fill(1)]).areas(side_area);
+        app.draw_panel(frame, Panel::Hourly, table_area);
+        app.draw_panel(frame, Panel::Details, details_area);
+        app.draw_panel(frame, Panel::Alerts, alerts_area);
+    }
+}
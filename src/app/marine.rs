@@ -0,0 +1,39 @@
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+};
+
+use super::{App, Page};
+use crate::{
+    data::marine,
+    layout::Breakpoint,
+    widgets::{daily_weather::DayView, marine_table::MarineTable},
+};
+
+/// Waves, swell and sea temperature hour by hour for the selected day
+pub(super) struct Marine;
+
+impl Page for Marine {
+    fn draw(&self, app: &mut App, frame: &mut Frame, area: Rect, _breakpoint: Breakpoint) {
+        let Some(forecast) = &app.marine else {
+            return;
+        };
+        let [day_area, table_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+
+        let mut day = app.daily.clone();
+        day.view(DayView::Single);
+        let selected_day = day.clone().selected();
+        frame.render_widget(
+            MarineTable::new(marine::marine_for(forecast, selected_day))
+                .theme(app.theme)
+                .icons(app.config.icons),
+            table_area,
+        );
+        app.draw_daily(frame, day, day_area);
+    }
+
+    fn moves_hours(&self) -> bool {
+        false
+    }
+}
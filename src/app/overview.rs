@@ -0,0 +1,41 @@
+use ratatui::{Frame, layout::Constraint, layout::Rect};
+
+use super::{App, Page};
+use crate::{
+    data::weather,
+    layout::{self, Breakpoint, Panel},
+};
+
+/// The dashboard of configurable panels
+pub(super) struct Overview;
+
+impl Page for Overview {
+    fn draw(&self, app: &mut App, frame: &mut Frame, area: Rect, breakpoint: Breakpoint) {
+        // The forecast's first week, the 14-day tab has the rest
+        app.daily
+            .visible(Some(app.config.forecast.past_days as usize + 7));
+        let selected_day = app.daily.clone().selected();
+        let has_pollen = weather::has_pollen(&app.pollen);
+        let has_nowcast = app.nowcast().is_some();
+        let has_astronomy = weather::astronomy_for(&app.weather.daily, selected_day).is_some();
+        let panels = layout::dashboard_layout(area, &app.layout, breakpoint, |panel| {
+            Constraint::Length(match panel {
+                Panel::Search => 3,
+                Panel::Title => 1,
+                Panel::Now => 2,
+                Panel::Warnings => u16::from(!app.warnings.is_empty()),
+                Panel::Daily => app.daily.height(),
+                Panel::AirQuality => u16::from(has_pollen),
+                Panel::Nowcast => u16::from(has_nowcast),
+                Panel::Details | Panel::Chart => 12,
+                Panel::Astronomy => u16::from(has_astronomy),
+                Panel::Status => 1,
+                Panel::Hourly | Panel::Alerts => return Constraint::Fill(1),
+            })
+        });
+
+        for (panel, area) in panels {
+            app.draw_panel(frame, panel, area);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use clap::Parser;
+
+use crate::{config::Config, icons::IconSet};
+
+/// Terminal weather dashboard
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Forecast model, e.g. ecmwf_ifs025, gfs_seamless or icon_seamless
+    #[arg(long)]
+    pub model: Option<String>,
+    /// Comma separated models to compare side by side
+    #[arg(long, value_delimiter = ',')]
+    pub compare: Vec<String>,
+    /// Forecast for this elevation in meters, e.g. a summit above the town
+    #[arg(long)]
+    pub elevation: Option<f32>,
+    /// dark, light, high-contrast, colorblind or a user theme name
+    #[arg(long)]
+    pub theme: Option<String>,
+    /// Weather icons: emoji, nerd-font or ascii
+    #[arg(long, value_enum)]
+    pub icons: Option<IconSet>,
+    /// Start in the big current conditions view, e.g. for a wall display
+    #[arg(long)]
+    pub big: bool,
+}
+
+impl Cli {
+    /// Options given on the command line take precedence over the config file
+    pub fn apply(self, config: &mut Config) {
+        if let Some(model) = self.model {
+            config.forecast.model = model;
+        }
+        if !self.compare.is_empty() {
+            config.forecast.compare_models = self.compare;
+        }
+        if self.elevation.is_some() {
+            config.forecast.elevation = self.elevation;
+        }
+        if self.theme.is_some() {
+            config.theme = self.theme;
+        }
+        if let Some(icons) = self.icons {
+            config.icons = icons;
+        }
+        config.big |= self.big;
+    }
+}
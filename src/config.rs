@@ -0,0 +1,240 @@
+use std::{collections::HashMap, error::Error, fs, path::PathBuf};
+
+use ratatui::{layout::Constraint, style::Color};
+use serde::Deserialize;
+
+use crate::{
+    data::alert_rules::AlertRule,
+    gradient::ColorDepth,
+    icons::IconSet,
+    keymap::{Action, KeyPreset},
+    layout::LayoutRow,
+    widgets::weather_table::Column,
+};
+
+/// User settings read from `<config dir>/weather/config.toml`. Every section is
+/// optional, anything left out falls back to the defaults below.
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct Config {
+    pub forecast: ForecastConfig,
+    pub hourly: HourlyConfig,
+    pub alerts: Vec<AlertRule>,
+    pub notifications: NotificationConfig,
+    pub warnings: WarningsConfig,
+    pub history: HistoryConfig,
+    pub ensemble: EnsembleConfig,
+    /// Built-in or user theme name, the dark theme when unset
+    pub theme: Option<String>,
+    /// `emoji`, `nerd-font` or `ascii`
+    pub icons: IconSet,
+    /// Start in the big current conditions view
+    pub big: bool,
+    /// Dashboard rows top to bottom, the built-in layout when empty
+    pub layout: Vec<LayoutRow>,
+    pub temperature_colors: TemperatureColorsConfig,
+    pub keys: KeysConfig,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ForecastConfig {
+    /// Days of recent weather shown before today, between 0 and 3
+    pub past_days: u8,
+    /// Hours of 15 minute precipitation nowcast, between 2 and 6
+    pub nowcast_hours: u8,
+    /// Open-Meteo model behind the forecast, `best_match` lets it choose
+    pub model: String,
+    /// Models shown side by side in the comparison table, none disables it
+    pub compare_models: Vec<String>,
+    /// Forecast for this elevation in meters instead of the location's
+    pub elevation: Option<f32>,
+}
+
+impl Default for ForecastConfig {
+    fn default() -> Self {
+        Self {
+            past_days: 1,
+            nowcast_hours: 6,
+            model: "best_match".to_string(),
+            compare_models: Vec::new(),
+            elevation: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct HourlyConfig {
+    /// Columns shown in the hourly table, in display order
+    pub columns: Vec<Column>,
+    /// Fixed column widths in cells, overriding the column's default
+    pub widths: HashMap<Column, u16>,
+}
+
+impl Default for HourlyConfig {
+    fn default() -> Self {
+        Self {
+            columns: vec![
+                Column::Time,
+                Column::Condition,
+                Column::Temp,
+                Column::PrecipProbability,
+            ],
+            widths: HashMap::new(),
+        }
+    }
+}
+
+impl HourlyConfig {
+    pub fn constraint(&self, column: Column) -> Constraint {
+        self.widths
+            .get(&column)
+            .map_or(column.default_width(), |&width| Constraint::Length(width))
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct NotificationConfig {
+    pub enabled: bool,
+    /// How far ahead to look for rain starting
+    pub rain_within_hours: u32,
+    /// Precipitation probability at which an hour counts as rainy
+    pub rain_probability: u16,
+    /// Also announce matches of the `[[alerts]]` rules
+    pub alerts: bool,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rain_within_hours: 2,
+            rain_probability: 50,
+            alerts: true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct WarningsConfig {
+    /// CAP 1.2 or ATOM feed URLs (or local file paths) read for every
+    /// location. `{lat}` and `{lon}` are replaced with the searched location.
+    pub feeds: Vec<String>,
+    /// Feeds only read for locations in a country, keyed by lowercase ISO
+    /// 3166-1 code
+    pub countries: HashMap<String, Vec<String>>,
+    /// `NAME:value` geocodes of the area to watch, e.g. `UGC:WAZ558` or
+    /// `EMMA_ID:NL001`, for warnings that carry no geometry
+    pub geocodes: Vec<String>,
+}
+
+impl Default for WarningsConfig {
+    fn default() -> Self {
+        Self {
+            feeds: Vec::new(),
+            // The NWS only covers the US, elsewhere there's no default feed
+            countries: HashMap::from([(
+                "us".to_string(),
+                vec!["https://api.weather.gov/alerts/active.atom?point={lat},{lon}".to_string()],
+            )]),
+            geocodes: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Base URL of the Open-Meteo archive API
+    pub archive_url: String,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            archive_url: "https://archive-api.open-meteo.com".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct EnsembleConfig {
+    /// Fetch ensemble members alongside every forecast refresh
+    pub enabled: bool,
+    /// Open-Meteo ensemble model, e.g. `icon_seamless`, `gfs_seamless` or
+    /// `ecmwf_ifs025`
+    pub model: String,
+    /// Base URL of the Open-Meteo ensemble API
+    pub url: String,
+}
+
+impl Default for EnsembleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: "icon_seamless".to_string(),
+            url: "https://ensemble-api.open-meteo.com".to_string(),
+        }
+    }
+}
+
+/// A color pinned to a temperature in °F, colors in between are blended
+#[derive(Debug, Deserialize, Clone)]
+pub struct GradientStop {
+    pub temp: f32,
+    pub color: Color,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct TemperatureColorsConfig {
+    /// Color temperatures in the hourly table and on the day cards
+    pub enabled: bool,
+    /// `auto` detects it from `COLORTERM` and `TERM`
+    pub depth: ColorDepth,
+    /// Hex colors only, in any order. Empty uses the theme's scale.
+    pub stops: Vec<GradientStop>,
+}
+
+impl Default for TemperatureColorsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            depth: ColorDepth::Auto,
+            stops: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct KeysConfig {
+    /// `default`, `vim` or `emacs`
+    pub preset: KeyPreset,
+    /// Keys for an action, replacing the preset's
+    pub bindings: HashMap<Action, Vec<String>>,
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("weather").join("config.toml"))
+    }
+
+    /// Load the config file, a missing file is not an error.
+    pub fn load() -> Result<Config, Box<dyn Error>> {
+        let Some(path) = Self::path().filter(|path| path.exists()) else {
+            return Ok(Config::default());
+        };
+
+        let contents = fs::read_to_string(&path)?;
+        let mut config: Config = toml::from_str(&contents)
+            .map_err(|err| format!("Invalid config {}: {}", path.display(), err))?;
+        config.forecast.past_days = config.forecast.past_days.min(3);
+        config.forecast.nowcast_hours = config.forecast.nowcast_hours.clamp(2, 6);
+        Ok(config)
+    }
+}
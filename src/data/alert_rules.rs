@@ -0,0 +1,245 @@
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use serde::Deserialize;
+
+use crate::data::weather::Weather;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    Temp,
+    FeelsLike,
+    PrecipProbability,
+    PrecipAmount,
+    Wind,
+    Gusts,
+    Humidity,
+    Uv,
+    CloudCover,
+    WeatherCode,
+}
+
+impl Metric {
+    /// `None` when the model has no value for the hour, which never matches
+    fn value(&self, weather: &Weather) -> Option<f32> {
+        let atmosphere = weather.atmosphere.clone().unwrap_or_default();
+        match self {
+            Metric::Temp => weather.temp,
+            Metric::FeelsLike => weather.apparent_temp,
+            Metric::PrecipProbability => weather.precip.map(f32::from),
+            Metric::PrecipAmount => atmosphere.precip_amount,
+            Metric::Wind => atmosphere.wind_speed,
+            Metric::Gusts => atmosphere.wind_gusts,
+            Metric::Humidity => atmosphere.humidity.map(f32::from),
+            Metric::Uv => atmosphere.uv_index,
+            Metric::CloudCover => atmosphere.cloud_cover.map(f32::from),
+            Metric::WeatherCode => weather.weather_code.map(f32::from),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Condition {
+    Below(f32),
+    Above(f32),
+    /// Inclusive on both ends
+    Between(f32, f32),
+}
+
+impl Condition {
+    fn matches(&self, value: f32) -> bool {
+        match *self {
+            Condition::Below(limit) => value < limit,
+            Condition::Above(limit) => value > limit,
+            Condition::Between(low, high) => value >= low && value <= high,
+        }
+    }
+}
+
+/// A user defined rule from the `[[alerts]]` config section, e.g.
+/// `{ name = "Freezing", metric = "temp", below = 32 }`. Exactly one of
+/// `below`, `above` or `between` has to be set.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(try_from = "RawAlertRule")]
+pub struct AlertRule {
+    pub name: String,
+    pub metric: Metric,
+    pub condition: Condition,
+}
+
+#[derive(Deserialize)]
+struct RawAlertRule {
+    name: String,
+    metric: Metric,
+    below: Option<f32>,
+    above: Option<f32>,
+    between: Option<[f32; 2]>,
+}
+
+impl TryFrom<RawAlertRule> for AlertRule {
+    type Error = String;
+
+    fn try_from(raw: RawAlertRule) -> Result<Self, Self::Error> {
+        let condition = match (raw.below, raw.above, raw.between) {
+            (Some(limit), None, None) => Condition::Below(limit),
+            (None, Some(limit), None) => Condition::Above(limit),
+            (None, None, Some([low, high])) => Condition::Between(low.min(high), low.max(high)),
+            _ => {
+                return Err(format!(
+                    "alert \"{}\" needs exactly one of `below`, `above` or `between`",
+                    raw.name
+                ));
+            }
+        };
+
+        Ok(AlertRule {
+            name: raw.name,
+            metric: raw.metric,
+            condition,
+        })
+    }
+}
+
+/// A run of consecutive forecast hours matching the same rule
+#[derive(Debug, Clone)]
+pub struct AlertSpan {
+    pub name: String,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+impl AlertSpan {
+    pub fn contains(&self, date_time: NaiveDateTime) -> bool {
+        date_time >= self.start && date_time <= self.end
+    }
+
+    pub fn touches(&self, date: NaiveDate) -> bool {
+        self.start.date() <= date && self.end.date() >= date
+    }
+
+    /// The last matching hour runs until an hour after it starts
+    pub fn is_over(&self, now: NaiveDateTime) -> bool {
+        self.end + TimeDelta::hours(1) <= now
+    }
+}
+
+/// Evaluate every rule against every forecast hour, merging back to back
+/// matches into a single span.
+pub fn evaluate(rules: &[AlertRule], hourly: &[Weather]) -> Vec<AlertSpan> {
+    let mut spans: Vec<AlertSpan> = Vec::new();
+
+    for rule in rules {
+        let mut current: Option<AlertSpan> = None;
+        for weather in hourly.iter().filter(|weather| {
+            rule.metric
+                .value(weather)
+                .is_some_and(|value| rule.condition.matches(value))
+        }) {
+            match current.as_mut() {
+                Some(span) if weather.date_time - span.end <= TimeDelta::hours(1) => {
+                    span.end = weather.date_time;
+                }
+                _ => {
+                    spans.extend(current.take());
+                    current = Some(AlertSpan {
+                        name: rule.name.clone(),
+                        start: weather.date_time,
+                        end: weather.date_time,
+                    });
+                }
+            }
+        }
+        spans.extend(current);
+    }
+
+    spans.sort_by_key(|span| span.start);
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Timelike;
+
+    use super::*;
+
+    fn at(hour: i64) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2024-01-10T00:00", "%Y-%m-%dT%H:%M").unwrap()
+            + TimeDelta::hours(hour)
+    }
+
+    fn hours(temps: &[Option<f32>]) -> Vec<Weather> {
+        temps
+            .iter()
+            .enumerate()
+            .map(|(i, &temp)| Weather {
+                temp,
+                date_time: at(i as i64),
+                ..Weather::default()
+            })
+            .collect()
+    }
+
+    fn rules(toml: &str) -> Result<Vec<AlertRule>, toml::de::Error> {
+        #[derive(Deserialize)]
+        struct Config {
+            alerts: Vec<AlertRule>,
+        }
+        toml::from_str::<Config>(toml).map(|config| config.alerts)
+    }
+
+    #[test]
+    fn consecutive_hours_merge_into_spans() {
+        let rules = rules(
+            r#"alerts = [
+                { name = "Freezing", metric = "temp", below = 32 },
+                { name = "Mild", metric = "temp", between = [40, 35] },
+            ]"#,
+        )
+        .unwrap();
+        let hourly = hours(&[
+            Some(30.0),
+            Some(28.0),
+            Some(36.0),
+            Some(31.0),
+            None,
+            Some(29.0),
+        ]);
+
+        let spans: Vec<_> = evaluate(&rules, &hourly)
+            .into_iter()
+            .map(|span| (span.name, span.start.hour(), span.end.hour()))
+            .collect();
+        // A missing hour breaks a span like a non-matching one
+        assert_eq!(
+            spans,
+            [
+                ("Freezing".to_string(), 0, 1),
+                ("Mild".to_string(), 2, 2),
+                ("Freezing".to_string(), 3, 3),
+                ("Freezing".to_string(), 5, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn rules_need_exactly_one_condition() {
+        assert!(rules(r#"alerts = [{ name = "Hot", metric = "temp", above = 90 }]"#).is_ok());
+        assert!(rules(r#"alerts = [{ name = "Hot", metric = "temp" }]"#).is_err());
+        assert!(
+            rules(r#"alerts = [{ name = "Hot", metric = "temp", above = 90, below = 100 }]"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn spans_end_an_hour_after_their_last_match() {
+        let span = AlertSpan {
+            name: "Freezing".to_string(),
+            start: at(0),
+            end: at(1),
+        };
+
+        assert!(span.contains(at(1)));
+        assert!(!span.is_over(at(1)));
+        assert!(span.is_over(at(2)));
+    }
+}
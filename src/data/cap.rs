@@ -0,0 +1,522 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use futures::future::join_all;
+use reqwest::Client;
+use roxmltree::{Document, Node};
+use std::error::Error;
+
+use crate::config::WarningsConfig;
+
+const CAP_NAMESPACE: &str = "urn:oasis:names:tc:emergency:cap:1.2";
+const EARTH_RADIUS_KM: f32 = 6371.0;
+
+/// A government warning from a Common Alerting Protocol (CAP 1.2) feed
+#[derive(Debug, Clone, Default)]
+pub struct CapAlert {
+    pub event: String,
+    pub severity: String,
+    pub area_desc: String,
+    pub effective: Option<DateTime<FixedOffset>>,
+    /// When the weather itself starts, often later than `effective`
+    pub onset: Option<DateTime<FixedOffset>>,
+    pub expires: Option<DateTime<FixedOffset>>,
+    /// Each polygon is a closed ring of (lat, lon) points
+    pub polygons: Vec<Vec<(f32, f32)>>,
+    /// (lat, lon, radius in km)
+    pub circles: Vec<(f32, f32, f32)>,
+    /// (value name, value) pairs such as `("UGC", "WAZ558")` or
+    /// `("EMMA_ID", "NL001")`
+    pub geocodes: Vec<(String, String)>,
+}
+
+impl CapAlert {
+    /// The onset, or when the alert takes effect for alerts without one
+    pub fn start(&self) -> Option<DateTime<FixedOffset>> {
+        self.onset.or(self.effective)
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// Started and not expired yet
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.start().is_none_or(|start| start <= now) && !self.is_expired(now)
+    }
+
+    /// Whether the alert covers the given point. Alerts without any geometry
+    /// (common in MeteoAlarm feeds, which only carry region codes) are
+    /// matched by the configured `geocodes` (e.g. `UGC:WAZ558`). Only alerts
+    /// without geocodes either fall back to their area names, each compared
+    /// whole against each part of the location name.
+    pub fn applies_to(
+        &self,
+        latitude: f32,
+        longitude: f32,
+        location_name: &str,
+        geocodes: &[String],
+    ) -> bool {
+        if !self.geocodes.is_empty() && self.polygons.is_empty() && self.circles.is_empty() {
+            return self.geocodes.iter().any(|(name, value)| {
+                geocodes.iter().any(|geocode| {
+                    geocode
+                        .split_once(':')
+                        .is_some_and(|(wanted_name, wanted)| {
+                            wanted_name.trim().eq_ignore_ascii_case(name)
+                                && wanted.trim().eq_ignore_ascii_case(value)
+                        })
+                })
+            });
+        }
+        if self.polygons.is_empty() && self.circles.is_empty() {
+            let places: Vec<String> = location_name.split(',').map(normalize_area).collect();
+            return self
+                .area_desc
+                .split([';', ','])
+                .map(normalize_area)
+                .any(|area| !area.is_empty() && places.contains(&area));
+        }
+
+        self.polygons
+            .iter()
+            .any(|polygon| point_in_polygon((latitude, longitude), polygon))
+            || self
+                .circles
+                .iter()
+                .any(|&(lat, lon, radius)| distance_km((latitude, longitude), (lat, lon)) <= radius)
+    }
+}
+
+/// Parse either a bare CAP 1.2 `<alert>` document or an ATOM feed whose
+/// entries carry `cap:` elements (NWS) or embed full CAP alerts.
+pub fn parse_feed(xml: &str) -> Result<Vec<CapAlert>, Box<dyn Error + Send + Sync>> {
+    let document = Document::parse(xml)?;
+    let root = document.root_element();
+
+    if root.tag_name().name() == "alert" {
+        return Ok(parse_alert(root));
+    }
+
+    let alerts = root
+        .children()
+        .filter(|node| node.tag_name().name() == "entry")
+        .flat_map(|entry| {
+            match entry
+                .descendants()
+                .find(|node| node.tag_name().name() == "alert")
+            {
+                Some(alert) => parse_alert(alert),
+                None => vec![parse_entry(entry)],
+            }
+        })
+        .collect();
+
+    Ok(alerts)
+}
+
+/// A full CAP alert has one `<info>` block per language, each with its own
+/// areas. Every block becomes a separate alert.
+fn parse_alert(alert: Node) -> Vec<CapAlert> {
+    alert
+        .children()
+        .filter(|node| node.tag_name().name() == "info")
+        .map(|info| {
+            let areas: Vec<Node> = info
+                .children()
+                .filter(|node| node.tag_name().name() == "area")
+                .collect();
+            CapAlert {
+                event: child_text(info, "event"),
+                severity: child_text(info, "severity"),
+                area_desc: areas
+                    .iter()
+                    .map(|area| child_text(*area, "areaDesc"))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                effective: parse_time(&child_text(info, "effective")),
+                onset: parse_time(&child_text(info, "onset")),
+                expires: parse_time(&child_text(info, "expires")),
+                polygons: areas
+                    .iter()
+                    .flat_map(|area| children_text(*area, "polygon"))
+                    .filter_map(|polygon| parse_polygon(&polygon))
+                    .collect(),
+                circles: areas
+                    .iter()
+                    .flat_map(|area| children_text(*area, "circle"))
+                    .filter_map(|circle| parse_circle(&circle))
+                    .collect(),
+                geocodes: areas
+                    .iter()
+                    .flat_map(|area| area.children())
+                    .filter(|node| node.tag_name().name() == "geocode")
+                    .flat_map(parse_geocodes)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// ATOM entries flatten the CAP fields into `cap:` prefixed children
+fn parse_entry(entry: Node) -> CapAlert {
+    let cap_text = |name: &str| {
+        entry
+            .children()
+            .find(|node| node.has_tag_name((CAP_NAMESPACE, name)))
+            .and_then(|node| node.text())
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+
+    CapAlert {
+        event: cap_text("event"),
+        severity: cap_text("severity"),
+        area_desc: cap_text("areaDesc"),
+        effective: parse_time(&cap_text("effective")),
+        onset: parse_time(&cap_text("onset")),
+        expires: parse_time(&cap_text("expires")),
+        polygons: parse_polygon(&cap_text("polygon")).into_iter().collect(),
+        circles: Vec::new(),
+        geocodes: entry
+            .children()
+            .filter(|node| node.has_tag_name((CAP_NAMESPACE, "geocode")))
+            .flat_map(parse_geocodes)
+            .collect(),
+    }
+}
+
+/// A `<geocode>` holds `valueName`/`value` pairs, one pair per element in
+/// CAP and several pairs back to back in NWS ATOM entries, which also list
+/// every code of a kind in one space separated value
+fn parse_geocodes(geocode: Node) -> Vec<(String, String)> {
+    let names = children_text(geocode, "valueName");
+    let values = children_text(geocode, "value");
+    names
+        .zip(values)
+        .flat_map(|(name, values)| {
+            values
+                .split_whitespace()
+                .map(|value| (name.clone(), value.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Lowercase words with punctuation and a trailing "County" dropped, so
+/// "King County" and "King" or "Noord-Holland" and "Noord Holland" compare
+/// equal
+fn normalize_area(area: &str) -> String {
+    let words: Vec<String> = area
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    match words.split_last() {
+        Some((last, rest)) if last == "county" && !rest.is_empty() => rest.join(" "),
+        _ => words.join(" "),
+    }
+}
+
+fn child_text(node: Node, name: &str) -> String {
+    children_text(node, name).next().unwrap_or_default()
+}
+
+fn children_text<'a>(node: Node<'a, 'a>, name: &'a str) -> impl Iterator<Item = String> + 'a {
+    node.children()
+        .filter(move |child| child.tag_name().name() == name)
+        .map(|child| child.text().unwrap_or_default().trim().to_string())
+}
+
+fn parse_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value).ok()
+}
+
+/// CAP polygons are whitespace separated `lat,lon` pairs
+fn parse_polygon(value: &str) -> Option<Vec<(f32, f32)>> {
+    let points: Vec<(f32, f32)> = value
+        .split_whitespace()
+        .filter_map(|pair| {
+            let (lat, lon) = pair.split_once(',')?;
+            Some((lat.parse().ok()?, lon.parse().ok()?))
+        })
+        .collect();
+
+    (points.len() >= 3).then_some(points)
+}
+
+/// CAP circles are `lat,lon radius` with the radius in kilometers
+fn parse_circle(value: &str) -> Option<(f32, f32, f32)> {
+    let (center, radius) = value.trim().split_once(' ')?;
+    let (lat, lon) = center.split_once(',')?;
+    Some((
+        lat.parse().ok()?,
+        lon.parse().ok()?,
+        radius.trim().parse().ok()?,
+    ))
+}
+
+/// Ray casting, treating lat/lon as planar which is fine at warning scale
+fn point_in_polygon((lat, lon): (f32, f32), polygon: &[(f32, f32)]) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (lat_i, lon_i) = polygon[i];
+        let (lat_j, lon_j) = polygon[j];
+        if (lat_i > lat) != (lat_j > lat)
+            && lon < (lon_j - lon_i) * (lat - lat_i) / (lat_j - lat_i) + lon_i
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+fn distance_km((lat1, lon1): (f32, f32), (lat2, lon2): (f32, f32)) -> f32 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Read a feed from a URL or, for anything that isn't http(s), a local file.
+/// `{lat}` and `{lon}` in the source are replaced with the location.
+async fn read_feed(
+    source: &str,
+    latitude: f32,
+    longitude: f32,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let source = source
+        .replace("{lat}", &latitude.to_string())
+        .replace("{lon}", &longitude.to_string());
+
+    if !source.starts_with("http://") && !source.starts_with("https://") {
+        return Ok(tokio::fs::read_to_string(source.trim_start_matches("file://")).await?);
+    }
+
+    let response = Client::new()
+        .get(&source)
+        .header("User-Agent", "rust-weather")
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.text().await?)
+}
+
+/// Warnings from every feed that cover the location and haven't expired,
+/// including ones still to start. Feeds are read at the same time, those
+/// that fail to load or parse are skipped.
+pub async fn fetch_warnings(
+    config: &WarningsConfig,
+    latitude: f32,
+    longitude: f32,
+    location_name: &str,
+    country_code: Option<&str>,
+) -> Vec<CapAlert> {
+    let country_feeds = country_code
+        .and_then(|code| config.countries.get(code))
+        .into_iter()
+        .flatten();
+    let feeds = join_all(
+        config
+            .feeds
+            .iter()
+            .chain(country_feeds)
+            .map(|feed| read_feed(feed, latitude, longitude)),
+    )
+    .await;
+
+    let now = Utc::now();
+    feeds
+        .into_iter()
+        .filter_map(|xml| parse_feed(&xml.ok()?).ok())
+        .flatten()
+        .filter(|alert| {
+            !alert.is_expired(now)
+                && alert.applies_to(latitude, longitude, location_name, &config.geocodes)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NWS_ATOM: &str = include_str!("../../tests/fixtures/nws_alerts.atom");
+    const METEOALARM_CAP: &str = include_str!("../../tests/fixtures/meteoalarm_alert.cap");
+
+    const SEATTLE: (f32, f32) = (47.6062, -122.3321);
+    const SEATTLE_NAME: &str = "Seattle, King County, Washington, United States";
+
+    #[test]
+    fn parse_nws_atom_feed() {
+        let alerts = parse_feed(NWS_ATOM).unwrap();
+
+        assert_eq!(alerts.len(), 2);
+        let storm = &alerts[0];
+        assert_eq!(storm.event, "Winter Storm Warning");
+        assert_eq!(storm.severity, "Severe");
+        assert_eq!(
+            storm.expires,
+            DateTime::parse_from_rfc3339("2024-01-13T16:00:00-08:00").ok()
+        );
+        assert_eq!(storm.polygons.len(), 1);
+        assert_eq!(storm.polygons[0].len(), 5);
+        assert!(
+            storm
+                .geocodes
+                .contains(&("UGC".to_string(), "WAZ558".to_string()))
+        );
+        assert!(
+            storm
+                .geocodes
+                .contains(&("UGC".to_string(), "WAZ507".to_string()))
+        );
+
+        let wind = &alerts[1];
+        assert_eq!(wind.event, "Wind Advisory");
+        assert!(wind.polygons.is_empty());
+        assert_eq!(wind.area_desc, "King, WA; Snohomish, WA");
+    }
+
+    #[test]
+    fn parse_meteoalarm_cap_alert() {
+        let alerts = parse_feed(METEOALARM_CAP).unwrap();
+
+        // One alert per language block
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].event, "GLÄTTE");
+        assert_eq!(alerts[1].event, "slippery roads");
+        assert_eq!(alerts[1].severity, "Moderate");
+        assert_eq!(alerts[1].area_desc, "District of Herzogtum Lauenburg");
+        assert_eq!(
+            alerts[1].geocodes,
+            [("EMMA_ID".to_string(), "DE412".to_string())]
+        );
+        assert!(alerts[1].polygons.is_empty() && alerts[1].circles.is_empty());
+    }
+
+    #[test]
+    fn parse_alert_areas() {
+        let xml = r#"<alert xmlns="urn:oasis:names:tc:emergency:cap:1.2">
+            <info>
+                <event>Flood Warning</event>
+                <severity>Extreme</severity>
+                <expires>not a time</expires>
+                <area>
+                    <areaDesc>River Town</areaDesc>
+                    <polygon>1,1 1,2 2,2 1,1</polygon>
+                    <polygon>1,1 1,2</polygon>
+                </area>
+                <area>
+                    <areaDesc>Lake Town</areaDesc>
+                    <circle>10.5,20.25 15</circle>
+                </area>
+            </info>
+        </alert>"#;
+        let document = Document::parse(xml).unwrap();
+        let alerts = parse_alert(document.root_element());
+
+        assert_eq!(alerts.len(), 1);
+        let alert = &alerts[0];
+        assert_eq!(alert.area_desc, "River Town; Lake Town");
+        assert_eq!(alert.expires, None);
+        // Polygons need at least three points
+        assert_eq!(
+            alert.polygons,
+            [vec![(1.0, 1.0), (1.0, 2.0), (2.0, 2.0), (1.0, 1.0)]]
+        );
+        assert_eq!(alert.circles, [(10.5, 20.25, 15.0)]);
+    }
+
+    #[test]
+    fn point_in_polygon_handles_concave_shapes() {
+        // A U shape open to the north
+        let polygon = [
+            (0.0, 0.0),
+            (0.0, 3.0),
+            (3.0, 3.0),
+            (3.0, 2.0),
+            (1.0, 2.0),
+            (1.0, 1.0),
+            (3.0, 1.0),
+            (3.0, 0.0),
+            (0.0, 0.0),
+        ];
+
+        assert!(point_in_polygon((0.5, 1.5), &polygon));
+        assert!(point_in_polygon((2.5, 0.5), &polygon));
+        assert!(!point_in_polygon((2.0, 1.5), &polygon));
+        assert!(!point_in_polygon((4.0, 1.5), &polygon));
+    }
+
+    #[test]
+    fn circle_matching() {
+        let alert = CapAlert {
+            // About 11 km around downtown Seattle
+            circles: vec![(47.6, -122.3, 11.0)],
+            ..CapAlert::default()
+        };
+
+        assert!(alert.applies_to(SEATTLE.0, SEATTLE.1, "", &[]));
+        // Tacoma is ~40 km south
+        assert!(!alert.applies_to(47.25, -122.44, "", &[]));
+    }
+
+    #[test]
+    fn nws_alerts_match_by_polygon_and_county() {
+        let alerts = parse_feed(NWS_ATOM).unwrap();
+
+        assert!(alerts[0].applies_to(SEATTLE.0, SEATTLE.1, SEATTLE_NAME, &[]));
+        // Portland is outside the polygon
+        assert!(!alerts[0].applies_to(45.52, -122.68, SEATTLE_NAME, &[]));
+        // Without a polygon the county code decides, not the area names
+        assert!(!alerts[1].applies_to(SEATTLE.0, SEATTLE.1, SEATTLE_NAME, &[]));
+        assert!(alerts[1].applies_to(
+            SEATTLE.0,
+            SEATTLE.1,
+            SEATTLE_NAME,
+            &["UGC:WAC033".to_string()]
+        ));
+    }
+
+    #[test]
+    fn area_names_match_whole_parts_only() {
+        let alert = CapAlert {
+            area_desc: "Washington; Kent".to_string(),
+            ..CapAlert::default()
+        };
+
+        assert!(alert.applies_to(0.0, 0.0, "Olympia, Thurston County, Washington", &[]));
+        assert!(!alert.applies_to(0.0, 0.0, "Washington Heights, Manhattan, New York", &[]));
+        assert!(!alert.applies_to(0.0, 0.0, "Kentucky, United States", &[]));
+    }
+
+    #[test]
+    fn alerts_are_active_from_their_onset() {
+        let alerts = parse_feed(METEOALARM_CAP).unwrap();
+        let alert = &alerts[1];
+        let at = |time: &str| DateTime::parse_from_rfc3339(time).unwrap().to_utc();
+
+        // In effect as a message at 11:00, the roads only get slippery at 18:00
+        assert_eq!(alert.start(), alert.onset);
+        assert!(!alert.is_active(at("2024-01-12T12:00:00+01:00")));
+        assert!(!alert.is_expired(at("2024-01-12T12:00:00+01:00")));
+        assert!(alert.is_active(at("2024-01-12T19:00:00+01:00")));
+        assert!(alert.is_expired(at("2024-01-13T10:00:00+01:00")));
+
+        let nws = parse_feed(NWS_ATOM).unwrap();
+        assert_eq!(nws[0].start(), nws[0].effective);
+    }
+
+    #[test]
+    fn geocodes_match_configured_codes() {
+        let alerts = parse_feed(METEOALARM_CAP).unwrap();
+        let location = "Mölln, Schleswig-Holstein, Deutschland";
+
+        assert!(!alerts[1].applies_to(53.6, 10.7, location, &[]));
+        assert!(alerts[1].applies_to(53.6, 10.7, location, &["emma_id:de412".to_string()]));
+        assert!(!alerts[1].applies_to(53.6, 10.7, location, &["EMMA_ID:DE41".to_string()]));
+    }
+}
@@ -0,0 +1,214 @@
+use std::{collections::HashMap, error::Error, fs, path::PathBuf};
+
+use chrono::{Datelike, NaiveDate};
+use iana_time_zone::get_timezone;
+use serde::{Deserialize, Serialize};
+
+use crate::data::weather::{OpenMeteoDaily, at};
+
+/// The WMO standard reference period
+const NORMALS_START: &str = "1991-01-01";
+const NORMALS_END: &str = "2020-12-31";
+/// Days either side of a date pooled into its normal, smoothing out the
+/// noise of single calendar days
+const WINDOW_DAYS: i32 = 7;
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ArchiveResponse {
+    daily: ArchiveDaily,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ArchiveDaily {
+    time: Vec<String>,
+    temperature_2m_max: Vec<Option<f32>>,
+    temperature_2m_min: Vec<Option<f32>>,
+}
+
+/// 30 years of observed daily highs and lows, keyed by day of year
+#[derive(Debug, Default, Clone)]
+pub struct Climatology {
+    days: Vec<(u32, f32, f32)>,
+}
+
+/// How a day's forecast compares to the climate normal for its date
+#[derive(Debug, Clone, Copy)]
+pub struct ClimateContext {
+    pub high_delta: f32,
+    pub low_delta: f32,
+    /// Share of historical highs for the date below the forecast high
+    pub percentile: u8,
+}
+
+impl Climatology {
+    fn from_archive(daily: ArchiveDaily) -> Self {
+        let days = daily
+            .time
+            .iter()
+            .zip(daily.temperature_2m_max)
+            .zip(daily.temperature_2m_min)
+            .filter_map(|((date, high), low)| {
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+                Some((date.ordinal(), high?, low?))
+            })
+            .collect();
+
+        Self { days }
+    }
+
+    pub fn compare(&self, date: NaiveDate, high: f32, low: f32) -> Option<ClimateContext> {
+        let ordinal = date.ordinal() as i32;
+        let window: Vec<(f32, f32)> = self
+            .days
+            .iter()
+            .filter(|(day, _, _)| {
+                // Wrap around the new year so early January pools with late December
+                let distance = (*day as i32 - ordinal).rem_euclid(366);
+                distance.min(366 - distance) <= WINDOW_DAYS
+            })
+            .map(|&(_, high, low)| (high, low))
+            .collect();
+
+        if window.is_empty() {
+            return None;
+        }
+
+        let count = window.len() as f32;
+        let normal_high = window.iter().map(|(high, _)| high).sum::<f32>() / count;
+        let normal_low = window.iter().map(|(_, low)| low).sum::<f32>() / count;
+        let below = window.iter().filter(|(past, _)| *past < high).count() as f32;
+
+        Some(ClimateContext {
+            high_delta: high - normal_high,
+            low_delta: low - normal_low,
+            percentile: (below / count * 100.0).round() as u8,
+        })
+    }
+
+    /// Context for every day in `daily`, keyed by its date string
+    pub fn compare_days(&self, daily: &OpenMeteoDaily) -> HashMap<String, ClimateContext> {
+        daily
+            .date
+            .iter()
+            .enumerate()
+            .filter_map(|(i, date)| {
+                let parsed = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+                let high = at(&daily.temperature_2m_max, i)?;
+                let low = at(&daily.temperature_2m_min, i)?;
+                Some((date.clone(), self.compare(parsed, high, low)?))
+            })
+            .collect()
+    }
+}
+
+fn cache_path(latitude: f32, longitude: f32) -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| {
+        dir.join("weather")
+            .join(format!("climate_{:.2}_{:.2}.json", latitude, longitude))
+    })
+}
+
+/// Load the normals for a location from the cache, fetching and caching them
+/// from the archive API the first time. Normals don't change, so the cache
+/// never expires.
+pub async fn fetch_climatology(
+    archive_url: &str,
+    latitude: f32,
+    longitude: f32,
+) -> Result<Climatology, Box<dyn Error + Send + Sync>> {
+    let cache = cache_path(latitude, longitude);
+    if let Some(cached) = cache
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str::<ArchiveResponse>(&contents).ok())
+    {
+        return Ok(Climatology::from_archive(cached.daily));
+    }
+
+    let time_zone = get_timezone()?;
+    let url = format!(
+        "{}/v1/archive?\
+        latitude={}&\
+        longitude={}&\
+        start_date={}&\
+        end_date={}&\
+        daily=temperature_2m_max,temperature_2m_min&\
+        temperature_unit=fahrenheit&\
+        timezone={}&",
+        archive_url.trim_end_matches('/'),
+        latitude,
+        longitude,
+        NORMALS_START,
+        NORMALS_END,
+        time_zone
+    );
+    let response = reqwest::get(&url).await?.error_for_status()?;
+    let result = response.json::<ArchiveResponse>().await?;
+
+    // A failed cache write only costs a refetch next time
+    if let Some(path) = cache
+        && let Ok(contents) = serde_json::to_string(&result)
+    {
+        let _ = path.parent().map(fs::create_dir_all);
+        let _ = fs::write(&path, contents);
+    }
+
+    Ok(Climatology::from_archive(result.daily))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn climatology(days: &[(&str, Option<f32>, Option<f32>)]) -> Climatology {
+        Climatology::from_archive(ArchiveDaily {
+            time: days.iter().map(|(date, ..)| date.to_string()).collect(),
+            temperature_2m_max: days.iter().map(|&(_, high, _)| high).collect(),
+            temperature_2m_min: days.iter().map(|&(.., low)| low).collect(),
+        })
+    }
+
+    fn date(date: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn normals_and_percentile_within_the_window() {
+        let climatology = climatology(&[
+            ("1991-07-01", Some(80.0), Some(60.0)),
+            ("1992-07-04", Some(84.0), Some(62.0)),
+            ("1993-07-08", Some(88.0), Some(64.0)),
+            ("1994-07-11", Some(92.0), Some(66.0)),
+            // Outside the week either side of July 4th
+            ("1995-07-20", Some(120.0), Some(90.0)),
+            // Missing values are left out rather than counted as zero
+            ("1996-07-04", None, Some(0.0)),
+        ]);
+
+        let context = climatology.compare(date("2024-07-04"), 90.0, 60.0).unwrap();
+        assert_eq!(context.high_delta, 90.0 - 86.0);
+        assert_eq!(context.low_delta, 60.0 - 63.0);
+        // Three of the four highs are below 90
+        assert_eq!(context.percentile, 75);
+
+        let context = climatology.compare(date("2024-07-04"), 80.0, 60.0).unwrap();
+        assert_eq!(context.percentile, 0);
+    }
+
+    #[test]
+    fn the_window_wraps_around_the_new_year() {
+        let climatology = climatology(&[
+            ("1991-12-29", Some(30.0), Some(20.0)),
+            ("1992-01-03", Some(40.0), Some(24.0)),
+        ]);
+
+        let context = climatology.compare(date("2025-01-01"), 35.0, 22.0).unwrap();
+        assert_eq!(context.high_delta, 0.0);
+        assert_eq!(context.percentile, 50);
+        assert!(
+            climatology
+                .compare(date("2025-06-01"), 80.0, 60.0)
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use std::{collections::HashMap, error::Error};
+
+use chrono::NaiveDateTime;
+use iana_time_zone::get_timezone;
+use serde::Deserialize;
+
+use crate::config::EnsembleConfig;
+
+#[derive(Debug, Deserialize)]
+struct EnsembleResponse {
+    hourly: EnsembleHourly,
+}
+
+/// The ensemble API returns the control run as `temperature_2m` and every
+/// member as its own `temperature_2m_memberNN` series
+#[derive(Debug, Deserialize)]
+struct EnsembleHourly {
+    time: Vec<String>,
+    #[serde(flatten)]
+    series: HashMap<String, Vec<Option<f32>>>,
+}
+
+/// Per-hour temperature spread across all ensemble members
+#[derive(Debug, Default, Clone)]
+pub struct EnsembleSpread {
+    pub date_time: Vec<NaiveDateTime>,
+    pub min: Vec<f32>,
+    pub median: Vec<f32>,
+    pub max: Vec<f32>,
+    /// Per-member daily highs, keyed by date string
+    daily_highs: HashMap<String, Vec<f32>>,
+}
+
+impl EnsembleSpread {
+    fn from_hourly(hourly: EnsembleHourly) -> Self {
+        let members: Vec<Vec<Option<f32>>> = hourly
+            .series
+            .into_iter()
+            .filter(|(name, _)| name.starts_with("temperature_2m"))
+            .map(|(_, values)| values)
+            .collect();
+
+        let mut spread = Self::default();
+        for (i, time) in hourly.time.iter().enumerate() {
+            let Ok(date_time) = NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M") else {
+                continue;
+            };
+            let mut values: Vec<f32> = members
+                .iter()
+                .filter_map(|member| member.get(i).copied().flatten())
+                .collect();
+            if values.is_empty() {
+                continue;
+            }
+            values.sort_by(f32::total_cmp);
+
+            spread.date_time.push(date_time);
+            spread.min.push(values[0]);
+            spread.median.push(median(&values));
+            spread.max.push(values[values.len() - 1]);
+        }
+
+        for member in members.iter() {
+            let mut highs: HashMap<String, f32> = HashMap::new();
+            for (time, value) in hourly.time.iter().zip(member) {
+                let (Some(date), Some(value)) = (time.split('T').next(), value) else {
+                    continue;
+                };
+                let high = highs.entry(date.to_string()).or_insert(*value);
+                *high = high.max(*value);
+            }
+            for (date, high) in highs {
+                spread.daily_highs.entry(date).or_default().push(high);
+            }
+        }
+
+        spread
+    }
+
+    /// Half the range of the members' highs for each day, shown as "±"
+    pub fn daily_high_ranges(&self) -> HashMap<String, f32> {
+        self.daily_highs
+            .iter()
+            .filter_map(|(date, highs)| {
+                let min = highs.iter().copied().reduce(f32::min)?;
+                let max = highs.iter().copied().reduce(f32::max)?;
+                Some((date.clone(), (max - min) / 2.0))
+            })
+            .collect()
+    }
+}
+
+/// Of sorted, non-empty values, averaging the middle two of an even count
+fn median(sorted: &[f32]) -> f32 {
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) / 2.0
+    } else {
+        sorted[middle]
+    }
+}
+
+pub async fn fetch_ensemble(
+    config: &EnsembleConfig,
+    latitude: f32,
+    longitude: f32,
+) -> Result<EnsembleSpread, Box<dyn Error + Send + Sync>> {
+    let time_zone = get_timezone()?;
+    let url = format!(
+        "{}/v1/ensemble?\
+        latitude={}&\
+        longitude={}&\
+        hourly=temperature_2m&\
+        models={}&\
+        temperature_unit=fahrenheit&\
+        timezone={}&\
+        forecast_days=7&",
+        config.url.trim_end_matches('/'),
+        latitude,
+        longitude,
+        config.model,
+        time_zone
+    );
+    let response = reqwest::get(&url).await?.error_for_status()?;
+    let result = response.json::<EnsembleResponse>().await?;
+
+    Ok(EnsembleSpread::from_hourly(result.hourly))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spread(json: &str) -> EnsembleSpread {
+        EnsembleSpread::from_hourly(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn spread_across_members() {
+        let spread = spread(
+            r#"{
+                "time": ["2024-07-04T12:00", "2024-07-04T13:00", "2024-07-05T12:00"],
+                "temperature_2m": [70.0, 72.0, 60.0],
+                "temperature_2m_member01": [74.0, null, 64.0],
+                "temperature_2m_member02": [68.0, 71.0, 62.0],
+                "temperature_2m_member03": [80.0, 75.0, null]
+            }"#,
+        );
+
+        assert_eq!(spread.min, [68.0, 71.0, 60.0]);
+        assert_eq!(spread.max, [80.0, 75.0, 64.0]);
+        // An even count averages the middle two, missing members are skipped
+        assert_eq!(spread.median, [72.0, 72.0, 62.0]);
+
+        let ranges = spread.daily_high_ranges();
+        assert_eq!(ranges["2024-07-04"], (80.0 - 71.0) / 2.0);
+        assert_eq!(ranges["2024-07-05"], (64.0 - 60.0) / 2.0);
+    }
+
+    #[test]
+    fn hours_without_members_are_dropped() {
+        let spread = spread(
+            r#"{
+                "time": ["2024-07-04T12:00", "2024-07-04T13:00"],
+                "temperature_2m": [null, 70.0],
+                "precipitation": [1.0, 1.0]
+            }"#,
+        );
+
+        assert_eq!(spread.date_time.len(), 1);
+        assert_eq!(spread.median, [70.0]);
+    }
+}
@@ -1,7 +1,7 @@
 use regex::Regex;
 use reqwest::Client;
 use serde::Deserialize;
-use std::error::Error;
+use std::{collections::HashMap, error::Error};
 
 /// A single geocoding result from OSM
 #[derive(Debug, Deserialize, Clone)]
@@ -12,6 +12,24 @@ pub struct OSMResponse {
     longitude: String,
     #[serde(rename = "display_name")]
     pub name: String,
+    /// Free-form OSM tags, `ele` holds the surveyed elevation of peaks,
+    /// passes and the like
+    #[serde(default)]
+    extratags: Option<HashMap<String, String>>,
+    /// Address parts, `country_code` is the lowercase ISO 3166-1 code
+    #[serde(default)]
+    address: Option<HashMap<String, String>>,
+}
+
+/// A geocoded search result
+#[derive(Debug, Clone)]
+pub struct Location {
+    pub name: String,
+    pub coordinates: (f32, f32),
+    /// Elevation in meters where OSM has one tagged
+    pub elevation: Option<f32>,
+    /// Lowercase ISO 3166-1 alpha-2 code, e.g. `us`
+    pub country_code: Option<String>,
 }
 
 /// Sanitize the search input to make it URL-safe and compatible with OSM
@@ -25,13 +43,11 @@ fn sanitize_input(input: &str) -> String {
 }
 
 /// Search OpenStreetMap Nominatim for a given location string
-pub async fn geocode(
-    search: &str,
-) -> Result<(String, Vec<(f32, f32)>), Box<dyn Error + Send + Sync>> {
+pub async fn geocode(search: &str) -> Result<Location, Box<dyn Error + Send + Sync>> {
     let sanitized = sanitize_input(search);
 
     let url = format!(
-        "https://nominatim.openstreetmap.org/search?q={}&format=json&limit=1",
+        "https://nominatim.openstreetmap.org/search?q={}&format=json&limit=1&extratags=1&addressdetails=1",
         sanitized
     );
     let client = Client::new();
@@ -41,14 +57,29 @@ pub async fn geocode(
         .send()
         .await?;
     let data = resp.json::<Vec<OSMResponse>>().await?;
+    let location = data
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("No location found for {}", search))?;
 
-    fn geoloaction(location: OSMResponse) -> (f32, f32) {
-        let lat: f32 = location.latitude.parse().unwrap();
-        let lon: f32 = location.longitude.parse().unwrap();
-        (lat, lon)
-    }
+    // Tagged as e.g. "2962" or "2962 m"
+    let elevation = location
+        .extratags
+        .as_ref()
+        .and_then(|tags| tags.get("ele"))
+        .and_then(|ele| ele.split_whitespace().next())
+        .and_then(|ele| ele.parse().ok());
 
-    let result = data.iter().map(|i| geoloaction(i.clone())).collect();
+    let country_code = location
+        .address
+        .as_ref()
+        .and_then(|address| address.get("country_code"))
+        .map(|code| code.to_lowercase());
 
-    Ok((data.first().unwrap().name.clone(), result))
+    Ok(Location {
+        coordinates: (location.latitude.parse()?, location.longitude.parse()?),
+        name: location.name,
+        elevation,
+        country_code,
+    })
 }
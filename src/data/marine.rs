@@ -0,0 +1,101 @@
+use std::error::Error;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use iana_time_zone::get_timezone;
+use serde::Deserialize;
+
+use crate::data::weather::at;
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoMarineResponse {
+    hourly: OpenMeteoMarine,
+}
+
+/// Hourly sea state. Points too far inland get `null` for every hour, which
+/// is how a location is told apart from a coastal one.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct OpenMeteoMarine {
+    #[serde(rename = "time")]
+    pub date_time: Vec<String>,
+    #[serde(default)]
+    pub wave_height: Vec<Option<f32>>,
+    #[serde(default)]
+    pub wave_direction: Vec<Option<f32>>,
+    #[serde(default)]
+    pub wave_period: Vec<Option<f32>>,
+    #[serde(default)]
+    pub swell_wave_height: Vec<Option<f32>>,
+    #[serde(default)]
+    pub swell_wave_direction: Vec<Option<f32>>,
+    #[serde(default)]
+    pub swell_wave_period: Vec<Option<f32>>,
+    #[serde(default)]
+    pub sea_surface_temperature: Vec<Option<f32>>,
+}
+
+/// Sea state for one hour. Heights are in feet, periods in seconds.
+#[derive(Debug, Default, Clone)]
+pub struct Marine {
+    pub date_time: NaiveDateTime,
+    pub wave_height: Option<f32>,
+    pub wave_direction: Option<f32>,
+    pub wave_period: Option<f32>,
+    pub swell_height: Option<f32>,
+    pub swell_direction: Option<f32>,
+    pub swell_period: Option<f32>,
+    pub sea_temp: Option<f32>,
+}
+
+impl OpenMeteoMarine {
+    pub fn has_data(&self) -> bool {
+        self.wave_height.iter().any(Option::is_some)
+            || self.sea_surface_temperature.iter().any(Option::is_some)
+    }
+}
+
+pub fn marine_for(data: &OpenMeteoMarine, date: NaiveDate) -> Vec<Marine> {
+    data.date_time
+        .iter()
+        .enumerate()
+        .filter_map(|(i, time)| {
+            let date_time = NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").ok()?;
+            (date_time.date() == date).then(|| Marine {
+                date_time,
+                wave_height: at(&data.wave_height, i),
+                wave_direction: at(&data.wave_direction, i),
+                wave_period: at(&data.wave_period, i),
+                swell_height: at(&data.swell_wave_height, i),
+                swell_direction: at(&data.swell_wave_direction, i),
+                swell_period: at(&data.swell_wave_period, i),
+                sea_temp: at(&data.sea_surface_temperature, i),
+            })
+        })
+        .collect()
+}
+
+/// Marine forecast for the location, `None` when the marine API has no data
+/// for it (i.e. the location isn't near the coast)
+pub async fn fetch_marine(
+    latitude: f32,
+    longitude: f32,
+    past_days: u8,
+) -> Result<Option<OpenMeteoMarine>, Box<dyn Error + Send + Sync>> {
+    let time_zone = get_timezone()?;
+
+    let url = format!(
+        "https://marine-api.open-meteo.com/v1/marine?\
+        latitude={}&\
+        longitude={}&\
+        hourly=wave_height,wave_direction,wave_period,swell_wave_height,swell_wave_direction,swell_wave_period,sea_surface_temperature&\
+        length_unit=imperial&\
+        temperature_unit=fahrenheit&\
+        timezone={}&\
+        forecast_days=7&\
+        past_days={}&",
+        latitude, longitude, time_zone, past_days
+    );
+    let response = reqwest::get(&url).await?.error_for_status()?;
+    let result = response.json::<OpenMeteoMarineResponse>().await?;
+
+    Ok(Some(result.hourly).filter(OpenMeteoMarine::has_data))
+}
@@ -1,2 +1,9 @@
+pub mod alert_rules;
+pub mod cap;
+pub mod climate;
+pub mod ensemble;
 pub mod location;
+pub mod marine;
+pub mod models;
+pub mod nowcast;
 pub mod weather;
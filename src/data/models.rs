@@ -0,0 +1,151 @@
+use std::{collections::HashMap, error::Error};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use iana_time_zone::get_timezone;
+use serde::Deserialize;
+
+use crate::config::ForecastConfig;
+
+/// Variables that can be compared across models
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelVariable {
+    Temp,
+    PrecipProbability,
+    PrecipAmount,
+    Wind,
+}
+
+impl ModelVariable {
+    pub const ALL: [ModelVariable; 4] = [
+        ModelVariable::Temp,
+        ModelVariable::PrecipProbability,
+        ModelVariable::PrecipAmount,
+        ModelVariable::Wind,
+    ];
+
+    fn key(self) -> &'static str {
+        match self {
+            ModelVariable::Temp => "temperature_2m",
+            ModelVariable::PrecipProbability => "precipitation_probability",
+            ModelVariable::PrecipAmount => "precipitation",
+            ModelVariable::Wind => "windspeed_10m",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ModelVariable::Temp => "Temperature",
+            ModelVariable::PrecipProbability => "Precip %",
+            ModelVariable::PrecipAmount => "Precip mm",
+            ModelVariable::Wind => "Wind",
+        }
+    }
+
+    pub fn format(self, value: f32) -> String {
+        match self {
+            ModelVariable::Temp => format!("{:.1}°F", value),
+            ModelVariable::PrecipProbability => format!("{:.0}%", value),
+            ModelVariable::PrecipAmount => format!("{:.1} mm", value),
+            ModelVariable::Wind => format!("{:.0} mph", value),
+        }
+    }
+
+    /// The variable after this one, `None` after the last
+    pub fn next(self) -> Option<ModelVariable> {
+        let i = Self::ALL.iter().position(|&variable| variable == self)?;
+        Self::ALL.get(i + 1).copied()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ComparisonResponse {
+    hourly: ComparisonHourly,
+}
+
+/// With several `models=` the forecast API suffixes every series with the
+/// model name, e.g. `temperature_2m_gfs_seamless`
+#[derive(Debug, Deserialize, Default, Clone)]
+struct ComparisonHourly {
+    time: Vec<String>,
+    #[serde(flatten)]
+    series: HashMap<String, Vec<Option<f32>>>,
+}
+
+/// The same hourly variables from several models
+#[derive(Debug, Default, Clone)]
+pub struct ModelComparison {
+    pub models: Vec<String>,
+    hourly: ComparisonHourly,
+}
+
+impl ModelComparison {
+    /// Each hour of `date` with one value per model, missing where a model
+    /// doesn't cover the location or hour
+    pub fn day(
+        &self,
+        variable: ModelVariable,
+        date: NaiveDate,
+    ) -> Vec<(NaiveDateTime, Vec<Option<f32>>)> {
+        let series: Vec<Option<&Vec<Option<f32>>>> = self
+            .models
+            .iter()
+            .map(|model| {
+                self.hourly
+                    .series
+                    .get(&format!("{}_{}", variable.key(), model))
+                    // A single model comes back without the suffix
+                    .or_else(|| {
+                        (self.models.len() == 1)
+                            .then(|| self.hourly.series.get(variable.key()))
+                            .flatten()
+                    })
+            })
+            .collect();
+
+        self.hourly
+            .time
+            .iter()
+            .enumerate()
+            .filter_map(|(i, time)| {
+                let date_time = NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").ok()?;
+                (date_time.date() == date).then(|| {
+                    let values = series
+                        .iter()
+                        .map(|values| values.and_then(|values| values.get(i).copied().flatten()))
+                        .collect();
+                    (date_time, values)
+                })
+            })
+            .collect()
+    }
+}
+
+pub async fn fetch_comparison(
+    forecast: &ForecastConfig,
+    latitude: f32,
+    longitude: f32,
+) -> Result<ModelComparison, Box<dyn Error + Send + Sync>> {
+    let time_zone = get_timezone()?;
+    let hourly = ModelVariable::ALL.map(|variable| variable.key()).join(",");
+    let models = forecast.compare_models.join(",");
+    let url = format!(
+        "https://api.open-meteo.com/v1/forecast?\
+        latitude={}&\
+        longitude={}&\
+        hourly={}&\
+        models={}&\
+        temperature_unit=fahrenheit&\
+        windspeed_unit=mph&\
+        timezone={}&\
+        forecast_days=7&\
+        past_days={}&",
+        latitude, longitude, hourly, models, time_zone, forecast.past_days
+    );
+    let response = reqwest::get(&url).await?.error_for_status()?;
+    let result = response.json::<ComparisonResponse>().await?;
+
+    Ok(ModelComparison {
+        models: forecast.compare_models.clone(),
+        hourly: result.hourly,
+    })
+}
@@ -0,0 +1,158 @@
+use chrono::{NaiveDateTime, TimeDelta};
+
+use crate::data::weather::OpenMeteoMinutely15;
+
+/// Precipitation in a 15 minute step that counts as rain, in mm
+const RAIN_THRESHOLD: f32 = 0.1;
+const STEP_MINUTES: i64 = 15;
+
+/// When rain starts or stops within the nowcast window
+#[derive(Debug, Clone, Default)]
+pub struct Nowcast {
+    /// Precipitation per 15 minutes from the step in progress onwards
+    pub precipitation: Vec<f32>,
+    pub raining: bool,
+    /// Time until the rain starts, or stops when it's already raining
+    pub change_in: Option<TimeDelta>,
+    /// How long the rain lasts once it starts, `None` when it runs past the
+    /// end of the window
+    pub lasting: Option<TimeDelta>,
+    pub window: TimeDelta,
+}
+
+impl Nowcast {
+    pub fn summary(&self) -> String {
+        let hours = self.window.num_hours();
+        match (self.raining, self.change_in, self.lasting) {
+            (false, None, _) => format!("No rain expected in the next {} h", hours),
+            (false, Some(start), Some(lasting)) => format!(
+                "Rain starting in {}, lasting ~{}",
+                format_duration(start),
+                format_duration(lasting)
+            ),
+            (false, Some(start), None) => format!(
+                "Rain starting in {}, lasting {} h+",
+                format_duration(start),
+                hours
+            ),
+            (true, Some(stop), _) => format!("Rain stopping in {}", format_duration(stop)),
+            (true, None, _) => format!("Rain for at least the next {} h", hours),
+        }
+    }
+}
+
+/// Summarize the steps from the one in progress at `now` onwards. Returns
+/// `None` when there's no nowcast covering `now`, e.g. for history, or when
+/// the model left any step in the window empty.
+pub fn nowcast(data: &OpenMeteoMinutely15, now: NaiveDateTime) -> Option<Nowcast> {
+    let steps: Vec<(NaiveDateTime, f32)> = data
+        .date_time
+        .iter()
+        .zip(data.precipitation.iter())
+        .filter_map(|(date_time, &precipitation)| {
+            let start = NaiveDateTime::parse_from_str(date_time, "%Y-%m-%dT%H:%M").ok()?;
+            Some((start, precipitation))
+        })
+        .filter(|(start, _)| *start + TimeDelta::minutes(STEP_MINUTES) > now)
+        .map(|(start, precipitation)| Some((start, precipitation?)))
+        .collect::<Option<_>>()?;
+
+    let &(first, first_precipitation) = steps.first()?;
+    if first > now {
+        return None;
+    }
+
+    let raining = first_precipitation >= RAIN_THRESHOLD;
+    let is_wet = |&(_, precipitation): &(NaiveDateTime, f32)| precipitation >= RAIN_THRESHOLD;
+    let window_end = steps
+        .last()
+        .map(|(start, _)| *start + TimeDelta::minutes(STEP_MINUTES))?;
+
+    let change = steps.iter().position(|step| is_wet(step) != raining);
+    let change_in = change.map(|i| steps[i].0 - now);
+    let lasting = match (raining, change) {
+        (false, Some(start)) => steps[start..]
+            .iter()
+            .find(|step| !is_wet(step))
+            .map(|(stop, _)| *stop - steps[start].0),
+        _ => None,
+    };
+
+    Some(Nowcast {
+        precipitation: steps
+            .iter()
+            .map(|(_, precipitation)| *precipitation)
+            .collect(),
+        raining,
+        change_in,
+        lasting,
+        window: window_end - first,
+    })
+}
+
+fn format_duration(duration: TimeDelta) -> String {
+    let minutes = duration.num_minutes().max(1);
+    if minutes < 60 {
+        format!("{} min", minutes)
+    } else if minutes % 60 == 0 {
+        format!("{} h", minutes / 60)
+    } else {
+        format!("{} h {} min", minutes / 60, minutes % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minutely(precipitation: &[Option<f32>]) -> OpenMeteoMinutely15 {
+        let start = NaiveDateTime::parse_from_str("2024-07-04T12:00", "%Y-%m-%dT%H:%M").unwrap();
+        OpenMeteoMinutely15 {
+            date_time: (0..precipitation.len())
+                .map(|i| {
+                    (start + TimeDelta::minutes(STEP_MINUTES * i as i64))
+                        .format("%Y-%m-%dT%H:%M")
+                        .to_string()
+                })
+                .collect(),
+            precipitation: precipitation.to_vec(),
+        }
+    }
+
+    fn at(time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").unwrap()
+    }
+
+    #[test]
+    fn rain_starting_and_stopping() {
+        let data = minutely(&[Some(0.0), Some(0.0), Some(0.5), Some(0.4), Some(0.0)]);
+        let nowcast = nowcast(&data, at("2024-07-04T12:05")).unwrap();
+
+        assert!(!nowcast.raining);
+        assert_eq!(nowcast.change_in, Some(TimeDelta::minutes(25)));
+        assert_eq!(nowcast.lasting, Some(TimeDelta::minutes(30)));
+        assert_eq!(
+            nowcast.summary(),
+            "Rain starting in 25 min, lasting ~30 min"
+        );
+    }
+
+    #[test]
+    fn missing_precipitation_has_no_nowcast() {
+        let data = minutely(&[Some(0.0), None, Some(0.0)]);
+        assert!(nowcast(&data, at("2024-07-04T12:00")).is_none());
+
+        let data = OpenMeteoMinutely15 {
+            precipitation: Vec::new(),
+            ..minutely(&[Some(0.0), Some(0.0)])
+        };
+        assert!(nowcast(&data, at("2024-07-04T12:00")).is_none());
+    }
+
+    #[test]
+    fn outside_the_window_has_no_nowcast() {
+        let data = minutely(&[Some(0.0), Some(0.0)]);
+        assert!(nowcast(&data, at("2024-07-04T11:00")).is_none());
+        assert!(nowcast(&data, at("2024-07-04T13:00")).is_none());
+    }
+}
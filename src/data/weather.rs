@@ -3,26 +3,98 @@ use iana_time_zone::get_timezone;
 use serde::Deserialize;
 use std::error::Error;
 
-#[derive(Debug, Deserialize, Default)]
+use crate::config::ForecastConfig;
+
+#[derive(Debug, Deserialize, Default, Clone)]
 pub struct OpenMeteoResponse {
     pub hourly: OpenMeteoHourly,
     pub daily: OpenMeteoDaily,
-    // pub current: OpenMeteoCurrent,
+    /// Missing from archive responses
+    #[serde(default)]
+    pub current: Option<OpenMeteoCurrent>,
+    #[serde(default)]
+    pub minutely_15: OpenMeteoMinutely15,
+    /// Elevation in meters the forecast was made for
+    #[serde(default)]
+    pub elevation: f32,
     // pub daily_units: DailyUnits,
 }
 
+/// Hourly series. The archive API leaves out forecast-only variables, only the
+/// `best_match` model fills every variable, and hours that haven't been
+/// processed yet come back as `null`. All of those read as `None`.
 #[derive(Debug, Deserialize, Default, Clone)]
 pub struct OpenMeteoHourly {
     #[serde(rename = "time")]
     pub date_time: Vec<String>,
-    pub temperature_2m: Vec<f32>,
-    pub apparent_temperature: Vec<f32>,
-    pub precipitation_probability: Vec<u16>,
-    // pub relative_humidity_2m: Vec<u32>,
-    #[serde(rename = "weathercode")]
-    pub weather_code: Vec<u16>,
-    // pub windspeed_10m: Vec<f32>,
-    // pub winddirection_10m: Vec<f32>,
+    #[serde(default)]
+    pub temperature_2m: Vec<Option<f32>>,
+    #[serde(default)]
+    pub apparent_temperature: Vec<Option<f32>>,
+    #[serde(default)]
+    pub precipitation_probability: Vec<Option<u16>>,
+    #[serde(default)]
+    pub precipitation: Vec<Option<f32>>,
+    #[serde(default)]
+    pub relative_humidity_2m: Vec<Option<u16>>,
+    #[serde(default)]
+    pub dew_point_2m: Vec<Option<f32>>,
+    #[serde(default)]
+    pub surface_pressure: Vec<Option<f32>>,
+    #[serde(default)]
+    pub cloud_cover: Vec<Option<u16>>,
+    #[serde(default)]
+    pub cloud_cover_low: Vec<Option<u16>>,
+    #[serde(default)]
+    pub cloud_cover_mid: Vec<Option<u16>>,
+    #[serde(default)]
+    pub cloud_cover_high: Vec<Option<u16>>,
+    #[serde(default)]
+    pub visibility: Vec<Option<f32>>,
+    #[serde(rename = "weathercode", alias = "weather_code", default)]
+    pub weather_code: Vec<Option<u16>>,
+    #[serde(default)]
+    pub windspeed_10m: Vec<Option<f32>>,
+    #[serde(default)]
+    pub winddirection_10m: Vec<Option<f32>>,
+    #[serde(default)]
+    pub wind_gusts_10m: Vec<Option<f32>>,
+    #[serde(default)]
+    pub uv_index: Vec<Option<f32>>,
+    /// Meters above sea level
+    #[serde(default)]
+    pub freezing_level_height: Vec<Option<f32>>,
+    #[serde(default, rename = "temperature_850hPa")]
+    pub temperature_850hpa: Vec<Option<f32>>,
+    #[serde(default, rename = "temperature_700hPa")]
+    pub temperature_700hpa: Vec<Option<f32>>,
+    #[serde(default, rename = "temperature_500hPa")]
+    pub temperature_500hpa: Vec<Option<f32>>,
+    #[serde(default, rename = "geopotential_height_850hPa")]
+    pub geopotential_height_850hpa: Vec<Option<f32>>,
+    #[serde(default, rename = "geopotential_height_700hPa")]
+    pub geopotential_height_700hpa: Vec<Option<f32>>,
+    #[serde(default, rename = "geopotential_height_500hPa")]
+    pub geopotential_height_500hpa: Vec<Option<f32>>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct OpenMeteoAirQualityResponse {
+    pub hourly: OpenMeteoPollen,
+}
+
+/// Hourly pollen concentrations in grains/m³. Open-Meteo only models pollen
+/// over Europe, everywhere else every value comes back as `null`.
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct OpenMeteoPollen {
+    #[serde(rename = "time")]
+    pub date_time: Vec<String>,
+    pub alder_pollen: Vec<Option<f32>>,
+    pub birch_pollen: Vec<Option<f32>>,
+    pub grass_pollen: Vec<Option<f32>>,
+    pub mugwort_pollen: Vec<Option<f32>>,
+    pub olive_pollen: Vec<Option<f32>>,
+    pub ragweed_pollen: Vec<Option<f32>>,
 }
 
 // #[derive(Debug, Deserialize, Default)]
@@ -36,12 +108,24 @@ pub struct OpenMeteoHourly {
 pub struct OpenMeteoDaily {
     #[serde(rename = "time")]
     pub date: Vec<String>,
-    pub weather_code: Vec<u16>,
-    // pub temperature_2m_min: Vec<f32>,
-    pub temperature_2m_max: Vec<f32>,
+    #[serde(default)]
+    pub weather_code: Vec<Option<u16>>,
+    #[serde(default)]
+    pub temperature_2m_min: Vec<Option<f32>>,
+    #[serde(default)]
+    pub temperature_2m_max: Vec<Option<f32>>,
     // pub apparent_temperature_min: Vec<f32>,
-    pub apparent_temperature_max: Vec<f32>,
-    pub precipitation_probability_max: Vec<u16>,
+    #[serde(default)]
+    pub apparent_temperature_max: Vec<Option<f32>>,
+    #[serde(default)]
+    pub precipitation_probability_max: Vec<Option<u16>>,
+    #[serde(default)]
+    pub sunrise: Vec<Option<String>>,
+    #[serde(default)]
+    pub sunset: Vec<Option<String>>,
+    /// Seconds between sunrise and sunset
+    #[serde(default)]
+    pub daylight_duration: Vec<Option<f32>>,
 }
 
 // #[derive(Debug, Deserialize, Default)]
@@ -53,16 +137,16 @@ pub struct OpenMeteoDaily {
 //     precipitation_probability_max: String,
 // }
 
-// #[derive(Debug, Deserialize, Default)]
-// pub struct OpenMeteoCurrent {
-//     #[serde(rename = "time")]
-//     pub date_time: String,
-//     pub temperature_2m: f32,
-//     pub weather_code: u16,
-//     pub apparent_temperature: f32,
-//     pub precipitation: f32,
-//     pub relative_humidity_2m: u32,
-// }
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct OpenMeteoCurrent {
+    #[serde(rename = "time")]
+    pub date_time: String,
+    pub temperature_2m: f32,
+    pub weather_code: u16,
+    pub apparent_temperature: f32,
+    pub precipitation: f32,
+    pub relative_humidity_2m: u16,
+}
 
 // #[derive(Debug, Deserialize, Default)]
 // struct CurrentUnits {
@@ -71,63 +155,169 @@ pub struct OpenMeteoDaily {
 //     pub relative_humidity_2m: String,
 // }
 
+/// Precipitation in 15 minute steps for the next few hours, the first step
+/// being the one in progress
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct OpenMeteoMinutely15 {
+    #[serde(rename = "time")]
+    pub date_time: Vec<String>,
+    #[serde(default)]
+    pub precipitation: Vec<Option<f32>>,
+}
+
+/// Without an `elevation` Open-Meteo uses its terrain model's height for
+/// the point
 pub async fn fetch_weather(
     latitude: f32,
     longitude: f32,
+    elevation: Option<f32>,
+    forecast: &ForecastConfig,
 ) -> Result<OpenMeteoResponse, Box<dyn Error + Send + Sync>> {
     let time_zone = get_timezone()?;
+    let elevation = elevation.map_or(String::new(), |elevation| {
+        format!("elevation={}&", elevation)
+    });
 
     let url = format!(
         "https://api.open-meteo.com/v1/forecast?\
         latitude={}&\
         longitude={}&\
-        hourly=temperature_2m,apparent_temperature,precipitation_probability,relative_humidity_2m,weathercode,windspeed_10m,winddirection_10m&\
-        daily=weather_code,temperature_2m_max,temperature_2m_min,apparent_temperature_max,apparent_temperature_min,precipitation_probability_max&\
+        hourly=temperature_2m,apparent_temperature,precipitation_probability,precipitation,relative_humidity_2m,dew_point_2m,surface_pressure,cloud_cover,cloud_cover_low,cloud_cover_mid,cloud_cover_high,visibility,weathercode,windspeed_10m,winddirection_10m,wind_gusts_10m,uv_index,freezing_level_height,temperature_850hPa,temperature_700hPa,temperature_500hPa,geopotential_height_850hPa,geopotential_height_700hPa,geopotential_height_500hPa&\
+        daily=weather_code,temperature_2m_max,temperature_2m_min,apparent_temperature_max,apparent_temperature_min,precipitation_probability_max,sunrise,sunset,daylight_duration&\
         current=temperature_2m,relative_humidity_2m,apparent_temperature,weather_code,precipitation&\
+        minutely_15=precipitation&\
         temperature_unit=fahrenheit&\
         windspeed_unit=mph&\
         timezone={}&\
-        forecast_days=7&",
-        latitude, longitude, time_zone
+        forecast_days=14&\
+        past_days={}&\
+        past_minutely_15=1&\
+        forecast_minutely_15={}&\
+        models={}&\
+        {}",
+        latitude,
+        longitude,
+        time_zone,
+        forecast.past_days,
+        forecast.nowcast_hours as u16 * 4,
+        forecast.model,
+        elevation
+    );
+    let request = reqwest::get(&url);
+    let response = request.await?.error_for_status()?;
+    let result = response.json::<OpenMeteoResponse>().await?;
+
+    Ok(result)
+}
+
+/// Observed weather for past dates from the Open-Meteo archive. The base URL
+/// is configurable so a local server can stand in for the real API.
+pub async fn fetch_history(
+    archive_url: &str,
+    latitude: f32,
+    longitude: f32,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<OpenMeteoResponse, Box<dyn Error + Send + Sync>> {
+    let url = history_url(
+        archive_url,
+        latitude,
+        longitude,
+        start,
+        end,
+        &get_timezone()?,
     );
     let request = reqwest::get(&url);
-    let response = request.await?;
+    let response = request.await?.error_for_status()?;
     let result = response.json::<OpenMeteoResponse>().await?;
 
     Ok(result)
 }
 
-pub fn get_weather_description(code: u16) -> (&'static str, &'static str) {
+fn history_url(
+    archive_url: &str,
+    latitude: f32,
+    longitude: f32,
+    start: NaiveDate,
+    end: NaiveDate,
+    time_zone: &str,
+) -> String {
+    format!(
+        "{}/v1/archive?\
+        latitude={}&\
+        longitude={}&\
+        start_date={}&\
+        end_date={}&\
+        hourly=temperature_2m,apparent_temperature,precipitation,relative_humidity_2m,dew_point_2m,surface_pressure,cloud_cover,cloud_cover_low,cloud_cover_mid,cloud_cover_high,weather_code,windspeed_10m,winddirection_10m,wind_gusts_10m&\
+        daily=weather_code,temperature_2m_max,temperature_2m_min,apparent_temperature_max,apparent_temperature_min,sunrise,sunset,daylight_duration&\
+        temperature_unit=fahrenheit&\
+        windspeed_unit=mph&\
+        timezone={}&",
+        archive_url.trim_end_matches('/'),
+        latitude,
+        longitude,
+        start.format("%Y-%m-%d"),
+        end.format("%Y-%m-%d"),
+        time_zone
+    )
+}
+
+pub async fn fetch_pollen(
+    latitude: f32,
+    longitude: f32,
+) -> Result<OpenMeteoPollen, Box<dyn Error + Send + Sync>> {
+    let time_zone = get_timezone()?;
+
+    // Seven days is the most the air quality API forecasts, later days show
+    // as missing
+    let url = format!(
+        "https://air-quality-api.open-meteo.com/v1/air-quality?\
+        latitude={}&\
+        longitude={}&\
+        hourly=alder_pollen,birch_pollen,grass_pollen,mugwort_pollen,olive_pollen,ragweed_pollen&\
+        timezone={}&\
+        forecast_days=7&",
+        latitude, longitude, time_zone
+    );
+    let request = reqwest::get(&url);
+    let response = request.await?.error_for_status()?;
+    let result = response.json::<OpenMeteoAirQualityResponse>().await?;
+
+    Ok(result.hourly)
+}
+
+/// Icons for the codes come from the configured `IconSet`
+pub fn get_weather_description(code: u16) -> &'static str {
     match code {
-        0 => ("Clear sky", "☀️"),
-        1 => ("Mainly clear", "🌤️"),
-        2 => ("Partly cloudy", "⛅"),
-        3 => ("Overcast", "☁️"),
-        45 => ("Fog", "🌫️"),
-        48 => ("Depositing rime fog", "🌫️❄️"),
-        51 => ("Light drizzle", "🌦️"),
-        53 => ("Moderate drizzle", "🌧️"),
-        55 => ("Dense drizzle", "🌧️"),
-        56 => ("Light freezing drizzle", "🌧️❄️"),
-        57 => ("Dense freezing drizzle", "🌧️❄️"),
-        61 => ("Slight rain", "🌦️"),
-        63 => ("Moderate rain", "🌧️"),
-        65 => ("Heavy rain", "🌧️🌧️"),
-        66 => ("Light freezing rain", "🌧️❄️"),
-        67 => ("Heavy freezing rain", "🌧️❄️❄️"),
-        71 => ("Slight snow fall", "🌨️"),
-        73 => ("Moderate snow fall", "🌨️❄️"),
-        75 => ("Heavy snow fall", "❄️❄️"),
-        77 => ("Snow grains", "🌨️🧂"),
-        80 => ("Slight rain showers", "🌦️"),
-        81 => ("Moderate rain showers", "🌧️"),
-        82 => ("Violent rain showers", "⛈️"),
-        85 => ("Slight snow showers", "🌨️"),
-        86 => ("Heavy snow showers", "❄️❄️"),
-        95 => ("Thunderstorm", "🌩️"),
-        96 => ("Thunderstorm w/ hail", "⛈️🧊"),
-        99 => ("Heavy TS w/ hail", "⛈️🧊🧊"),
-        _ => ("Unknown", "❓"),
+        0 => "Clear sky",
+        1 => "Mainly clear",
+        2 => "Partly cloudy",
+        3 => "Overcast",
+        45 => "Fog",
+        48 => "Depositing rime fog",
+        51 => "Light drizzle",
+        53 => "Moderate drizzle",
+        55 => "Dense drizzle",
+        56 => "Light freezing drizzle",
+        57 => "Dense freezing drizzle",
+        61 => "Slight rain",
+        63 => "Moderate rain",
+        65 => "Heavy rain",
+        66 => "Light freezing rain",
+        67 => "Heavy freezing rain",
+        71 => "Slight snow fall",
+        73 => "Moderate snow fall",
+        75 => "Heavy snow fall",
+        77 => "Snow grains",
+        80 => "Slight rain showers",
+        81 => "Moderate rain showers",
+        82 => "Violent rain showers",
+        85 => "Slight snow showers",
+        86 => "Heavy snow showers",
+        95 => "Thunderstorm",
+        96 => "Thunderstorm w/ hail",
+        99 => "Heavy TS w/ hail",
+        _ => "Unknown",
     }
 }
 
@@ -141,17 +331,121 @@ pub fn get_cardinal_direction(degrees: f32) -> &'static str {
     directions[index]
 }
 
+pub fn meters_to_feet(meters: f32) -> f32 {
+    meters * 3.28084
+}
+
+/// Temperature at one pressure level and the height it sits at
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Level {
+    pub pressure: u16,
+    /// Meters above sea level
+    pub height: Option<f32>,
+    pub temp: Option<f32>,
+}
+
+/// Temperatures at several altitudes above the same point for one hour
+#[derive(Debug, Default, Clone)]
+pub struct Mountain {
+    pub date_time: NaiveDateTime,
+    /// Meters above sea level
+    pub freezing_level: Option<f32>,
+    pub surface_temp: Option<f32>,
+    pub levels: [Level; 3],
+}
+
+pub fn mountain_for(data: &OpenMeteoResponse, date: NaiveDate) -> Vec<Mountain> {
+    let hourly = &data.hourly;
+    hourly
+        .date_time
+        .iter()
+        .enumerate()
+        .filter_map(|(i, time)| {
+            let date_time = NaiveDateTime::parse_from_str(time, "%Y-%m-%dT%H:%M").ok()?;
+            (date_time.date() == date).then(|| Mountain {
+                date_time,
+                freezing_level: at(&hourly.freezing_level_height, i),
+                surface_temp: at(&hourly.temperature_2m, i),
+                levels: [
+                    Level {
+                        pressure: 850,
+                        height: at(&hourly.geopotential_height_850hpa, i),
+                        temp: at(&hourly.temperature_850hpa, i),
+                    },
+                    Level {
+                        pressure: 700,
+                        height: at(&hourly.geopotential_height_700hpa, i),
+                        temp: at(&hourly.temperature_700hpa, i),
+                    },
+                    Level {
+                        pressure: 500,
+                        height: at(&hourly.geopotential_height_500hpa, i),
+                        temp: at(&hourly.temperature_500hpa, i),
+                    },
+                ],
+            })
+        })
+        .collect()
+}
+
 pub enum WeatherQuery {
     Daily { date: NaiveDate },
     Hourly { date_time: NaiveDateTime },
 }
 
+/// Values the model didn't provide are `None` and shown as missing
+#[derive(Debug, Default, Clone)]
 pub struct Weather {
-    pub weather_code: u16,
-    pub precip: u16,
-    pub temp: f32,
-    pub apparent_temp: f32,
+    pub weather_code: Option<u16>,
+    pub precip: Option<u16>,
+    pub temp: Option<f32>,
+    pub apparent_temp: Option<f32>,
     pub date_time: NaiveDateTime,
+    pub atmosphere: Option<Atmosphere>,
+}
+
+/// Atmospheric conditions, only available for hourly lookups
+#[derive(Debug, Default, Clone)]
+pub struct Atmosphere {
+    /// Millimeters
+    pub precip_amount: Option<f32>,
+    pub humidity: Option<u16>,
+    pub dew_point: Option<f32>,
+    pub pressure: Option<f32>,
+    pub cloud_cover: Option<u16>,
+    pub cloud_cover_low: Option<u16>,
+    pub cloud_cover_mid: Option<u16>,
+    pub cloud_cover_high: Option<u16>,
+    /// Meters
+    pub visibility: Option<f32>,
+    pub wind_speed: Option<f32>,
+    pub wind_direction: Option<f32>,
+    pub wind_gusts: Option<f32>,
+    pub uv_index: Option<f32>,
+}
+
+/// Missing series (e.g. precipitation probability in the archive) and `null`
+/// values both read as `None`
+pub fn at<T: Copy>(values: &[Option<T>], i: usize) -> Option<T> {
+    values.get(i).copied().flatten()
+}
+
+fn atmosphere_at(hourly: &OpenMeteoHourly, i: usize) -> Atmosphere {
+    Atmosphere {
+        precip_amount: at(&hourly.precipitation, i),
+        humidity: at(&hourly.relative_humidity_2m, i),
+        dew_point: at(&hourly.dew_point_2m, i),
+        pressure: at(&hourly.surface_pressure, i),
+        cloud_cover: at(&hourly.cloud_cover, i),
+        cloud_cover_low: at(&hourly.cloud_cover_low, i),
+        cloud_cover_mid: at(&hourly.cloud_cover_mid, i),
+        cloud_cover_high: at(&hourly.cloud_cover_high, i),
+        visibility: at(&hourly.visibility, i),
+        wind_speed: at(&hourly.windspeed_10m, i),
+        wind_direction: at(&hourly.winddirection_10m, i),
+        wind_gusts: at(&hourly.wind_gusts_10m, i),
+        uv_index: at(&hourly.uv_index, i),
+    }
 }
 
 pub fn hourly_weather_for(data: &OpenMeteoResponse, date: NaiveDate) -> Vec<Weather> {
@@ -167,23 +461,32 @@ pub fn hourly_weather_for(data: &OpenMeteoResponse, date: NaiveDate) -> Vec<Weat
         })
         .position(|parssed_date| parssed_date == date)
         .unwrap_or_default();
-    for i in start_index..start_index + 24 {
-        vec.push(Weather {
-            date_time: NaiveDateTime::parse_from_str(
-                data.hourly.date_time[i].as_str(),
-                "%Y-%m-%dT%H:%M",
-            )
-            .unwrap_or_default(),
-            weather_code: data.hourly.weather_code[i],
-            temp: data.hourly.temperature_2m[i],
-            apparent_temp: data.hourly.apparent_temperature[i],
-            precip: data.hourly.precipitation_probability[i],
-        });
+    for i in start_index..(start_index + 24).min(data.hourly.date_time.len()) {
+        vec.push(hourly_weather_at(&data.hourly, i));
     }
 
     vec
 }
 
+/// Every hour in the forecast, in order
+pub fn hourly_weather(data: &OpenMeteoResponse) -> Vec<Weather> {
+    (0..data.hourly.date_time.len())
+        .map(|i| hourly_weather_at(&data.hourly, i))
+        .collect()
+}
+
+fn hourly_weather_at(hourly: &OpenMeteoHourly, i: usize) -> Weather {
+    Weather {
+        date_time: NaiveDateTime::parse_from_str(hourly.date_time[i].as_str(), "%Y-%m-%dT%H:%M")
+            .unwrap_or_default(),
+        weather_code: at(&hourly.weather_code, i),
+        temp: at(&hourly.temperature_2m, i),
+        apparent_temp: at(&hourly.apparent_temperature, i),
+        precip: at(&hourly.precipitation_probability, i),
+        atmosphere: Some(atmosphere_at(hourly, i)),
+    }
+}
+
 pub fn weather_lookup(data: &OpenMeteoResponse, query: WeatherQuery) -> Option<Weather> {
     match query {
         WeatherQuery::Daily { date } => {
@@ -194,12 +497,14 @@ pub fn weather_lookup(data: &OpenMeteoResponse, query: WeatherQuery) -> Option<W
                 .map(|date_str| NaiveDate::parse_from_str(date_str, "%Y-%m-%d").unwrap_or_default())
                 .position(|parsed_date| parsed_date == date)?;
             Some(Weather {
-                weather_code: data.daily.weather_code[i],
-                temp: data.daily.temperature_2m_max[i],
-                precip: data.daily.precipitation_probability_max[i],
-                apparent_temp: data.daily.apparent_temperature_max[i],
-                date_time: NaiveDateTime::parse_from_str(data.daily.date[i].as_str(), "%Y-%m-%d")
-                    .ok()?,
+                weather_code: at(&data.daily.weather_code, i),
+                temp: at(&data.daily.temperature_2m_max, i),
+                precip: at(&data.daily.precipitation_probability_max, i),
+                apparent_temp: at(&data.daily.apparent_temperature_max, i),
+                date_time: NaiveDate::parse_from_str(data.daily.date[i].as_str(), "%Y-%m-%d")
+                    .ok()?
+                    .into(),
+                atmosphere: None,
             })
         }
         WeatherQuery::Hourly { date_time } => {
@@ -211,17 +516,236 @@ pub fn weather_lookup(data: &OpenMeteoResponse, query: WeatherQuery) -> Option<W
                     NaiveDateTime::parse_from_str(date_str, "%Y-%m-%dT%H:%M").unwrap_or_default()
                 })
                 .position(|parsed_date| parsed_date == date_time)?;
-            Some(Weather {
-                date_time: NaiveDateTime::parse_from_str(
-                    data.hourly.date_time[i].as_str(),
-                    "%Y-%m-%dT%H:%M",
-                )
-                .ok()?,
-                weather_code: data.hourly.weather_code[i],
-                temp: data.hourly.temperature_2m[i],
-                precip: data.hourly.precipitation_probability[i],
-                apparent_temp: data.hourly.apparent_temperature[i],
-            })
+            Some(hourly_weather_at(&data.hourly, i))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PollenLevel {
+    None,
+    Low,
+    Moderate,
+    High,
+    VeryHigh,
+}
+
+impl PollenLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PollenLevel::None => "None",
+            PollenLevel::Low => "Low",
+            PollenLevel::Moderate => "Moderate",
+            PollenLevel::High => "High",
+            PollenLevel::VeryHigh => "Very high",
         }
     }
 }
+
+pub struct Pollen {
+    pub name: &'static str,
+    pub level: PollenLevel,
+}
+
+/// Classify a concentration using the National Allergy Bureau scale, which
+/// buckets trees, grasses and weeds differently.
+pub fn get_pollen_level(name: &str, grains: f32) -> PollenLevel {
+    let (moderate, high, very_high) = match name {
+        "Grass" => (5.0, 20.0, 200.0),
+        "Mugwort" | "Ragweed" => (10.0, 50.0, 500.0),
+        _ => (15.0, 90.0, 1500.0),
+    };
+
+    match grains {
+        g if g >= very_high => PollenLevel::VeryHigh,
+        g if g >= high => PollenLevel::High,
+        g if g >= moderate => PollenLevel::Moderate,
+        g if g >= 1.0 => PollenLevel::Low,
+        _ => PollenLevel::None,
+    }
+}
+
+/// Sunrise, sunset and length of one day
+#[derive(Debug, Clone, Copy)]
+pub struct Astronomy {
+    pub sunrise: NaiveDateTime,
+    pub sunset: NaiveDateTime,
+    /// Seconds of daylight
+    pub daylight: f32,
+    /// Change in daylight from the day before, in seconds
+    pub change: Option<f32>,
+}
+
+pub fn astronomy_for(data: &OpenMeteoDaily, date: NaiveDate) -> Option<Astronomy> {
+    let i = data
+        .date
+        .iter()
+        .position(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").is_ok_and(|day| day == date))?;
+    let parse = |times: &[Option<String>]| {
+        NaiveDateTime::parse_from_str(times.get(i)?.as_deref()?, "%Y-%m-%dT%H:%M").ok()
+    };
+    let daylight = at(&data.daylight_duration, i)?;
+
+    Some(Astronomy {
+        sunrise: parse(&data.sunrise)?,
+        sunset: parse(&data.sunset)?,
+        daylight,
+        change: i
+            .checked_sub(1)
+            .and_then(|previous| at(&data.daylight_duration, previous))
+            .map(|previous| daylight - previous),
+    })
+}
+
+/// Whether pollen is modelled for the location at all
+pub fn has_pollen(data: &OpenMeteoPollen) -> bool {
+    [
+        &data.alder_pollen,
+        &data.birch_pollen,
+        &data.grass_pollen,
+        &data.mugwort_pollen,
+        &data.olive_pollen,
+        &data.ragweed_pollen,
+    ]
+    .iter()
+    .any(|values| values.iter().any(Option::is_some))
+}
+
+/// Daily peak for each pollen type on `date`. Types with no data for the day
+/// are left out, so an empty result means there is nothing to show.
+pub fn daily_pollen_for(data: &OpenMeteoPollen, date: NaiveDate) -> Vec<Pollen> {
+    let indices: Vec<usize> = data
+        .date_time
+        .iter()
+        .enumerate()
+        .filter(|(_, date_time)| {
+            NaiveDateTime::parse_from_str(date_time, "%Y-%m-%dT%H:%M")
+                .is_ok_and(|parsed| parsed.date() == date)
+        })
+        .map(|(i, _)| i)
+        .collect();
+
+    let series: [(&'static str, &Vec<Option<f32>>); 6] = [
+        ("Alder", &data.alder_pollen),
+        ("Birch", &data.birch_pollen),
+        ("Grass", &data.grass_pollen),
+        ("Mugwort", &data.mugwort_pollen),
+        ("Olive", &data.olive_pollen),
+        ("Ragweed", &data.ragweed_pollen),
+    ];
+
+    series
+        .into_iter()
+        .filter_map(|(name, values)| {
+            let max = indices
+                .iter()
+                .filter_map(|&i| values.get(i).copied().flatten())
+                .reduce(f32::max)?;
+            Some(Pollen {
+                name,
+                level: get_pollen_level(name, max),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_values_stay_missing() {
+        let hourly: OpenMeteoHourly = serde_json::from_str(
+            r#"{
+                "time": ["2024-07-04T00:00", "2024-07-04T01:00"],
+                "temperature_2m": [71.2, null],
+                "precipitation_probability": [null, null]
+            }"#,
+        )
+        .unwrap();
+
+        let first = hourly_weather_at(&hourly, 0);
+        assert_eq!(first.temp, Some(71.2));
+        assert_eq!(first.precip, None);
+        assert_eq!(first.atmosphere.unwrap().visibility, None);
+        assert_eq!(hourly_weather_at(&hourly, 1).temp, None);
+    }
+
+    #[test]
+    fn single_model_nulls_are_missing() {
+        // What `models=gfs_seamless` returns for variables only best_match fills
+        let response: OpenMeteoResponse = serde_json::from_str(
+            r#"{
+                "hourly": {
+                    "time": ["2024-07-04T12:00"],
+                    "temperature_2m": [80.1],
+                    "precipitation_probability": [null],
+                    "visibility": [null],
+                    "uv_index": [null]
+                },
+                "daily": { "time": ["2024-07-04"], "precipitation_probability_max": [null] },
+                "minutely_15": {
+                    "time": ["2024-07-04T12:00", "2024-07-04T12:15"],
+                    "precipitation": [null, null]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let weather = hourly_weather(&response).remove(0);
+        let atmosphere = weather.atmosphere.unwrap();
+        assert_eq!(weather.precip, None);
+        assert_eq!(atmosphere.visibility, None);
+        assert_eq!(atmosphere.uv_index, None);
+        let date = NaiveDate::from_ymd_opt(2024, 7, 4).unwrap();
+        let daily = weather_lookup(&response, WeatherQuery::Daily { date }).unwrap();
+        assert_eq!(daily.precip, None);
+
+        let now = date.and_hms_opt(12, 5, 0).unwrap();
+        assert!(crate::data::nowcast::nowcast(&response.minutely_15, now).is_none());
+    }
+
+    #[test]
+    fn history_url_asks_the_archive_for_the_range() {
+        let url = history_url(
+            "http://localhost:8080/",
+            47.6,
+            -122.3,
+            NaiveDate::from_ymd_opt(2024, 7, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 7, 4).unwrap(),
+            "America/Los_Angeles",
+        );
+        let (base, query) = url.split_once('?').unwrap();
+        let params: Vec<(&str, &str)> = query
+            .split('&')
+            .filter_map(|param| param.split_once('='))
+            .collect();
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        };
+
+        assert_eq!(base, "http://localhost:8080/v1/archive");
+        assert_eq!(param("latitude"), Some("47.6"));
+        assert_eq!(param("longitude"), Some("-122.3"));
+        assert_eq!(param("start_date"), Some("2024-07-01"));
+        assert_eq!(param("end_date"), Some("2024-07-04"));
+        assert_eq!(param("temperature_unit"), Some("fahrenheit"));
+        assert_eq!(param("windspeed_unit"), Some("mph"));
+        assert_eq!(param("timezone"), Some("America/Los_Angeles"));
+        assert!(
+            param("hourly")
+                .unwrap()
+                .split(',')
+                .any(|v| v == "weather_code")
+        );
+        assert!(
+            param("daily")
+                .unwrap()
+                .split(',')
+                .any(|v| v == "temperature_2m_max")
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use std::env;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::config::{GradientStop, TemperatureColorsConfig};
+use crate::theme::Theme;
+
+/// How many colors the terminal can show
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorDepth {
+    /// Detect from `COLORTERM` and `TERM`
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    #[serde(rename = "256")]
+    Indexed,
+    #[serde(rename = "16")]
+    Ansi,
+}
+
+impl ColorDepth {
+    pub fn detect() -> ColorDepth {
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        let term = env::var("TERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Indexed
+        } else {
+            ColorDepth::Ansi
+        }
+    }
+}
+
+type Rgb = (u8, u8, u8);
+
+/// Where a theme's temperature colors sit on the scale, in °F
+const THEME_STOPS: [f32; 7] = [0.0, 20.0, 40.0, 60.0, 75.0, 90.0, 105.0];
+
+/// Channel values of the 6x6x6 cube in the xterm 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 16 ANSI colors with the RGB values xterm uses for them
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Maps temperatures to colors by interpolating between configured stops,
+/// reduced to what the terminal can show
+#[derive(Debug, Clone, Default)]
+pub struct TemperatureGradient {
+    /// Sorted by temperature, empty when disabled
+    stops: Vec<(f32, Rgb)>,
+    depth: ColorDepth,
+}
+
+impl TemperatureGradient {
+    /// Named and indexed colors are blended as their xterm RGB values, only
+    /// `reset` has none. The theme's scale is used when the config has no
+    /// stops, falling back to the dark theme's when it can't be blended.
+    pub fn new(config: &TemperatureColorsConfig, theme: &Theme) -> Result<Self, String> {
+        if !config.enabled {
+            return Ok(Self::default());
+        }
+
+        let mut stops = if config.stops.is_empty() {
+            theme_stops(theme).unwrap_or_else(|| {
+                eprintln!(
+                    "The theme's temperature colors can't be blended, using the default scale"
+                );
+                theme_stops(&Theme::dark()).unwrap_or_default()
+            })
+        } else {
+            config
+                .stops
+                .iter()
+                .map(|GradientStop { temp, color }| match to_rgb(*color) {
+                    Some(rgb) => Ok((*temp, rgb)),
+                    None => Err(format!(
+                        "Temperature color for {}° must be a color like \"#3288bd\" or \"blue\"",
+                        temp
+                    )),
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let depth = match config.depth {
+            ColorDepth::Auto => ColorDepth::detect(),
+            depth => depth,
+        };
+
+        Ok(Self { stops, depth })
+    }
+
+    /// `None` when no gradient is configured
+    pub fn color(&self, temp: f32) -> Option<Color> {
+        let first = self.stops.first()?;
+        let last = self.stops.last()?;
+
+        let rgb = if temp <= first.0 {
+            first.1
+        } else if temp >= last.0 {
+            last.1
+        } else {
+            let upper = self.stops.iter().position(|(stop, _)| *stop >= temp)?;
+            let (low_temp, low) = self.stops[upper - 1];
+            let (high_temp, high) = self.stops[upper];
+            let t = (temp - low_temp) / (high_temp - low_temp);
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            (mix(low.0, high.0), mix(low.1, high.1), mix(low.2, high.2))
+        };
+
+        Some(self.reduce(rgb))
+    }
+
+    fn reduce(&self, (r, g, b): (u8, u8, u8)) -> Color {
+        match self.depth {
+            ColorDepth::TrueColor | ColorDepth::Auto => Color::Rgb(r, g, b),
+            // The nearer of the xterm palette's 6x6x6 cube and its gray ramp
+            ColorDepth::Indexed => {
+                let level = |v: u8| {
+                    (0..6)
+                        .min_by_key(|&i| CUBE_LEVELS[i as usize].abs_diff(v))
+                        .unwrap_or_default()
+                };
+                let (lr, lg, lb) = (level(r), level(g), level(b));
+                let cube = |i: u8| CUBE_LEVELS[i as usize];
+                // 24 grays from 8 to 238 in steps of 10
+                let average = (r as u16 + g as u16 + b as u16) / 3;
+                let gray_step = ((average.saturating_sub(8) + 5) / 10).min(23) as u8;
+                let gray = 8 + 10 * gray_step;
+                if distance((gray, gray, gray), (r, g, b))
+                    < distance((cube(lr), cube(lg), cube(lb)), (r, g, b))
+                {
+                    Color::Indexed(232 + gray_step)
+                } else {
+                    Color::Indexed(16 + 36 * lr + 6 * lg + lb)
+                }
+            }
+            ColorDepth::Ansi => ANSI
+                .iter()
+                .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+                .map_or(Color::Reset, |(color, _)| *color),
+        }
+    }
+}
+
+fn theme_stops(theme: &Theme) -> Option<Vec<(f32, Rgb)>> {
+    THEME_STOPS
+        .iter()
+        .zip(theme.temperature)
+        .map(|(&temp, color)| Some((temp, to_rgb(color)?)))
+        .collect()
+}
+
+/// The RGB value xterm shows a color as, `None` for the terminal's default
+fn to_rgb(color: Color) -> Option<Rgb> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(i @ 0..=15) => Some(ANSI[i as usize].1),
+        Color::Indexed(i @ 16..=231) => {
+            let i = i - 16;
+            let level = |v: u8| CUBE_LEVELS[v as usize];
+            Some((level(i / 36), level(i / 6 % 6), level(i % 6)))
+        }
+        Color::Indexed(i) => {
+            let gray = 8 + 10 * (i - 232);
+            Some((gray, gray, gray))
+        }
+        named => ANSI
+            .iter()
+            .find(|(ansi, _)| *ansi == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+/// Squared distance between two RGB colors
+fn distance((ar, ag, ab): (u8, u8, u8), (br, bg, bb): (u8, u8, u8)) -> i32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+    d(ar, br) + d(ag, bg) + d(ab, bb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient(depth: ColorDepth, theme: &Theme) -> TemperatureGradient {
+        let config = TemperatureColorsConfig {
+            depth,
+            ..TemperatureColorsConfig::default()
+        };
+        TemperatureGradient::new(&config, theme).unwrap()
+    }
+
+    #[test]
+    fn blends_between_stops() {
+        let config = TemperatureColorsConfig {
+            depth: ColorDepth::TrueColor,
+            stops: vec![
+                GradientStop {
+                    temp: 100.0,
+                    color: Color::Rgb(200, 0, 0),
+                },
+                GradientStop {
+                    temp: 0.0,
+                    color: Color::Rgb(0, 0, 200),
+                },
+            ],
+            ..TemperatureColorsConfig::default()
+        };
+        let gradient = TemperatureGradient::new(&config, &Theme::dark()).unwrap();
+
+        assert_eq!(gradient.color(-20.0), Some(Color::Rgb(0, 0, 200)));
+        assert_eq!(gradient.color(25.0), Some(Color::Rgb(50, 0, 150)));
+        assert_eq!(gradient.color(120.0), Some(Color::Rgb(200, 0, 0)));
+    }
+
+    #[test]
+    fn default_stops_follow_the_theme() {
+        let dark = gradient(ColorDepth::TrueColor, &Theme::dark());
+        let light = gradient(ColorDepth::TrueColor, &Theme::light());
+
+        assert_eq!(dark.color(60.0), Some(Theme::dark().temperature[3]));
+        assert_eq!(light.color(60.0), Some(Theme::light().temperature[3]));
+    }
+
+    #[test]
+    fn reduces_to_the_xterm_palette() {
+        let gradient = TemperatureGradient {
+            depth: ColorDepth::Indexed,
+            ..TemperatureGradient::default()
+        };
+
+        // Cube levels are 0, 95, 135, 175, 215 and 255, not evenly spaced
+        assert_eq!(
+            gradient.reduce((95, 135, 175)),
+            Color::Indexed(16 + 36 + 12 + 3)
+        );
+        assert_eq!(gradient.reduce((255, 100, 0)), Color::Indexed(16 + 180 + 6));
+        assert_eq!(gradient.reduce((0, 0, 0)), Color::Indexed(16));
+        // Grays between the cube's levels use the gray ramp
+        assert_eq!(gradient.reduce((118, 118, 118)), Color::Indexed(243));
+
+        let gradient = TemperatureGradient {
+            depth: ColorDepth::Ansi,
+            ..gradient
+        };
+        assert_eq!(gradient.reduce((250, 10, 10)), Color::LightRed);
+    }
+
+    #[test]
+    fn themes_with_named_colors_load() {
+        let theme: Theme = toml::from_str(
+            r##"temperature = ["blue", "light-blue", "33", "white", "yellow", "#ff8000", "red"]"##,
+        )
+        .unwrap();
+        let gradient = gradient(ColorDepth::TrueColor, &theme);
+
+        assert_eq!(gradient.color(0.0), Some(Color::Rgb(0, 0, 238)));
+        assert_eq!(gradient.color(40.0), Some(Color::Rgb(0, 135, 255)));
+        assert_eq!(gradient.color(105.0), Some(Color::Rgb(205, 0, 0)));
+    }
+
+    #[test]
+    fn unusable_theme_colors_fall_back_to_the_default_scale() {
+        let mut theme = Theme::light();
+        theme.temperature[3] = Color::Reset;
+        let gradient = gradient(ColorDepth::TrueColor, &theme);
+
+        assert_eq!(gradient.color(60.0), Some(Theme::dark().temperature[3]));
+    }
+
+    #[test]
+    fn xterm_palette_to_rgb() {
+        assert_eq!(to_rgb(Color::Indexed(9)), Some((255, 0, 0)));
+        assert_eq!(
+            to_rgb(Color::Indexed(16 + 36 + 12 + 3)),
+            Some((95, 135, 175))
+        );
+        assert_eq!(to_rgb(Color::Indexed(243)), Some((118, 118, 118)));
+        assert_eq!(to_rgb(Color::DarkGray), Some((127, 127, 127)));
+        assert_eq!(to_rgb(Color::Reset), None);
+    }
+}
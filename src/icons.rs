@@ -0,0 +1,215 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use unicode_width::UnicodeWidthStr;
+
+/// Glyphs for weather conditions and the small markers next to values.
+/// Every icon of a set is padded to the same display width so table columns
+/// and card lines stay aligned whatever glyph a code maps to.
+#[derive(Debug, Deserialize, ValueEnum, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconSet {
+    #[default]
+    Emoji,
+    /// Weather glyphs from a patched Nerd Font
+    NerdFont,
+    /// Plain ASCII for consoles and SSH sessions without emoji
+    Ascii,
+}
+
+impl IconSet {
+    /// Display width every icon is padded to
+    fn width(self) -> usize {
+        match self {
+            IconSet::Emoji | IconSet::NerdFont => 2,
+            IconSet::Ascii => 3,
+        }
+    }
+
+    fn pad(self, icon: &str) -> String {
+        let padding = self.width().saturating_sub(icon.width());
+        format!("{}{}", icon, " ".repeat(padding))
+    }
+
+    /// One glyph per WMO weather code
+    pub fn weather(self, code: u16) -> String {
+        let icon = match self {
+            IconSet::Emoji => match code {
+                0 => "☀️",
+                1 => "🌤️",
+                2 => "⛅",
+                3 => "☁️",
+                45 | 48 => "🌫️",
+                51 | 61 | 80 => "🌦️",
+                53 | 55 | 63 | 65 | 81 => "🌧️",
+                56 | 57 | 66 | 67 => "🧊",
+                71 | 73 | 85 => "🌨️",
+                75 | 77 | 86 => "❄️",
+                82 | 96 | 99 => "⛈️",
+                95 => "🌩️",
+                _ => "❓",
+            },
+            IconSet::NerdFont => match code {
+                0 => "\u{e30d}",
+                1 | 2 => "\u{e302}",
+                3 => "\u{e312}",
+                45 | 48 => "\u{e313}",
+                51 | 53 | 55 => "\u{e31b}",
+                61 | 63 | 65 => "\u{e318}",
+                56 | 57 | 66 | 67 => "\u{e3ad}",
+                71 | 73 | 75 | 77 | 85 | 86 => "\u{e31a}",
+                80..=82 => "\u{e319}",
+                95 => "\u{e31d}",
+                96 | 99 => "\u{e314}",
+                _ => "\u{e374}",
+            },
+            IconSet::Ascii => match code {
+                0 => "(O)",
+                1 => "(O~",
+                2 => "~O~",
+                3 => "~~~",
+                45 | 48 => "===",
+                51 | 53 | 55 => "','",
+                61 | 80 => "/ /",
+                63 | 65 | 81 | 82 => "///",
+                56 | 57 | 66 | 67 => "/*/",
+                71 | 73 | 75 | 85 | 86 => "***",
+                77 => "...",
+                95 => "~!~",
+                96 | 99 => "!o!",
+                _ => "???",
+            },
+        };
+        self.pad(icon)
+    }
+
+    pub fn temperature(self) -> String {
+        self.pad(match self {
+            IconSet::Emoji => "🌡️",
+            IconSet::NerdFont => "\u{e350}",
+            IconSet::Ascii => "T",
+        })
+    }
+
+    /// Marks the feels-like temperature, cold below 50°F
+    pub fn feels_like(self, temp: f32) -> String {
+        let cold = temp < 50.0;
+        self.pad(match (self, cold) {
+            (IconSet::Emoji, true) => "🥶",
+            (IconSet::Emoji, false) => "🥵",
+            (IconSet::NerdFont, true) => "\u{e36f}",
+            (IconSet::NerdFont, false) => "\u{e30d}",
+            (IconSet::Ascii, true) => "FL-",
+            (IconSet::Ascii, false) => "FL+",
+        })
+    }
+
+    pub fn precipitation(self) -> String {
+        self.pad(match self {
+            IconSet::Emoji => "☔",
+            IconSet::NerdFont => "\u{e37d}",
+            IconSet::Ascii => "P",
+        })
+    }
+
+    pub fn marine(self) -> String {
+        self.pad(match self {
+            IconSet::Emoji => "🌊",
+            IconSet::NerdFont | IconSet::Ascii => "~",
+        })
+    }
+
+    // The markers below sit inline in text, so they aren't padded
+
+    /// Marks weather alerts and official warnings
+    pub fn warning(self) -> &'static str {
+        match self {
+            IconSet::Emoji => "⚠",
+            IconSet::NerdFont => "\u{f071}",
+            IconSet::Ascii => "!",
+        }
+    }
+
+    /// Filled and empty cells of a level meter
+    pub fn meter(self) -> (&'static str, &'static str) {
+        match self {
+            IconSet::Emoji | IconSet::NerdFont => ("■", "□"),
+            IconSet::Ascii => ("#", "."),
+        }
+    }
+
+    /// A line drawn across one chart cell
+    pub fn line(self) -> &'static str {
+        match self {
+            IconSet::Emoji | IconSet::NerdFont => "━",
+            IconSet::Ascii => "-",
+        }
+    }
+
+    pub fn arrow_up(self) -> &'static str {
+        match self {
+            IconSet::Emoji | IconSet::NerdFont => "↑",
+            IconSet::Ascii => "^",
+        }
+    }
+
+    pub fn arrow_down(self) -> &'static str {
+        match self {
+            IconSet::Emoji | IconSet::NerdFont => "↓",
+            IconSet::Ascii => "v",
+        }
+    }
+
+    pub fn arrow_right(self) -> &'static str {
+        match self {
+            IconSet::Emoji | IconSet::NerdFont => "→",
+            IconSet::Ascii => "->",
+        }
+    }
+
+    /// Fills the strokes of the big digits
+    pub fn block(self) -> char {
+        match self {
+            IconSet::Emoji | IconSet::NerdFont => '█',
+            IconSet::Ascii => '#',
+        }
+    }
+
+    /// Goes before the unit, plain ASCII has no degree sign
+    pub fn degree(self) -> &'static str {
+        match self {
+            IconSet::Emoji | IconSet::NerdFont => "°",
+            IconSet::Ascii => "",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_set_is_ascii() {
+        let icons = IconSet::Ascii;
+        let (full, empty) = icons.meter();
+        let mut glyphs = vec![
+            icons.temperature(),
+            icons.feels_like(-5.0),
+            icons.feels_like(30.0),
+            icons.precipitation(),
+            icons.marine(),
+            icons.warning().to_string(),
+            icons.line().to_string(),
+            icons.arrow_up().to_string(),
+            icons.arrow_down().to_string(),
+            icons.arrow_right().to_string(),
+            full.to_string(),
+            empty.to_string(),
+            icons.block().to_string(),
+            icons.degree().to_string(),
+        ];
+        glyphs.extend((0..=99).map(|code| icons.weather(code)));
+        for glyph in glyphs {
+            assert!(glyph.is_ascii(), "{:?} isn't ASCII", glyph);
+        }
+    }
+}
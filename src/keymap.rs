@@ -0,0 +1,640 @@
+use std::{collections::HashMap, fmt};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::config::KeysConfig;
+
+/// Everything a key can be bound to
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Quit,
+    Refresh,
+    Help,
+    Columns,
+    History,
+    ToggleEnsemble,
+    CycleComparison,
+    ToggleMountain,
+    ToggleBig,
+    #[serde(rename = "tab-1")]
+    Tab1,
+    #[serde(rename = "tab-2")]
+    Tab2,
+    #[serde(rename = "tab-3")]
+    Tab3,
+    #[serde(rename = "tab-4")]
+    Tab4,
+    #[serde(rename = "tab-5")]
+    Tab5,
+    #[serde(rename = "tab-6")]
+    Tab6,
+    #[serde(rename = "tab-7")]
+    Tab7,
+    NextDay,
+    PreviousDay,
+    NextHour,
+    PreviousHour,
+    PageDown,
+    PageUp,
+    FocusSearch,
+    Search,
+    LeaveSearch,
+    ClearSearch,
+    Select,
+    ClosePopup,
+    Confirm,
+    ToggleColumn,
+}
+
+/// Where an action's keys are listened for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    Anywhere,
+    /// Only while typing in the search bar
+    Search,
+    /// Only while the search bar isn't focused
+    Forecast,
+    /// Only while a popup is open, which takes the keys from the rest
+    Popup,
+}
+
+impl Action {
+    /// In the order the help popup lists them
+    pub const ALL: [Action; 30] = [
+        Action::Quit,
+        Action::Refresh,
+        Action::Help,
+        Action::Tab1,
+        Action::Tab2,
+        Action::Tab3,
+        Action::Tab4,
+        Action::Tab5,
+        Action::Tab6,
+        Action::Tab7,
+        Action::Columns,
+        Action::History,
+        Action::ToggleEnsemble,
+        Action::CycleComparison,
+        Action::ToggleMountain,
+        Action::ToggleBig,
+        Action::NextDay,
+        Action::PreviousDay,
+        Action::NextHour,
+        Action::PreviousHour,
+        Action::PageDown,
+        Action::PageUp,
+        Action::FocusSearch,
+        Action::Select,
+        Action::Search,
+        Action::LeaveSearch,
+        Action::ClearSearch,
+        Action::ClosePopup,
+        Action::Confirm,
+        Action::ToggleColumn,
+    ];
+
+    pub fn context(&self) -> Context {
+        match self {
+            Action::Search | Action::LeaveSearch | Action::ClearSearch => Context::Search,
+            Action::FocusSearch | Action::Select => Context::Forecast,
+            Action::ClosePopup | Action::Confirm | Action::ToggleColumn => Context::Popup,
+            _ => Context::Anywhere,
+        }
+    }
+
+    /// As written in the config, e.g. `next-day` or `tab-1`
+    pub fn name(&self) -> String {
+        let mut name = String::new();
+        for c in format!("{:?}", self).chars() {
+            if !name.is_empty() && (c.is_uppercase() || c.is_ascii_digit()) {
+                name.push('-');
+            }
+            name.push(c.to_ascii_lowercase());
+        }
+        name
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Exit the app",
+            Action::Refresh => "Refresh weather data",
+            Action::Help => "Show or hide this help",
+            Action::Columns => "Show or hide hourly columns",
+            Action::History => "Look up past weather",
+            Action::ToggleEnsemble => "Toggle ensemble spread chart",
+            Action::CycleComparison => "Cycle model comparison table",
+            Action::ToggleMountain => "Toggle mountain view",
+            Action::ToggleBig => "Toggle big current conditions",
+            Action::Tab1 => "Overview tab",
+            Action::Tab2 => "Hourly tab",
+            Action::Tab3 => "Charts tab",
+            Action::Tab4 => "14-day tab",
+            Action::Tab5 => "History tab",
+            Action::Tab6 => "Details tab",
+            Action::Tab7 => "Marine tab, for coastal locations",
+            Action::NextDay => "Next day",
+            Action::PreviousDay => "Previous day",
+            Action::NextHour => "Next hour, next day on the 14-day tab, down in popups",
+            Action::PreviousHour => "Previous hour, previous day on the 14-day tab, up in popups",
+            Action::PageDown => "Hourly table down a page",
+            Action::PageUp => "Hourly table up a page",
+            Action::FocusSearch => "Focus the search bar",
+            Action::Search => "Search for location weather",
+            Action::LeaveSearch => "Leave the search bar",
+            Action::ClearSearch => "Clear the search",
+            Action::Select => "Open the day's hours, or pick past dates",
+            Action::ClosePopup => "Close the popup",
+            Action::Confirm => "Look up the dates, or close the popup",
+            Action::ToggleColumn => "Show or hide the highlighted column",
+        }
+    }
+}
+
+/// Built-in sets of bindings, the user's own are applied on top
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeyPreset {
+    #[default]
+    Default,
+    /// Adds `hjkl` movement, `Ctrl+D`/`Ctrl+U` paging and `q` to quit
+    Vim,
+    /// Moves with `Ctrl+N`/`P`/`F`/`B`, pages with `Ctrl+V`/`Alt+V`; the
+    /// view toggles those displace move to `Alt`
+    Emacs,
+}
+
+impl KeyPreset {
+    /// Keys the search bar keeps for editing while it's focused, even when
+    /// they're bound to an action
+    fn text_keys(self) -> &'static [&'static str] {
+        match self {
+            KeyPreset::Default | KeyPreset::Vim => &[],
+            KeyPreset::Emacs => &["ctrl+f", "ctrl+b", "ctrl+n", "ctrl+p"],
+        }
+    }
+
+    fn bindings(self) -> Vec<(Action, Vec<&'static str>)> {
+        let mut bindings = vec![
+            (Action::Quit, vec!["ctrl+c"]),
+            (Action::Refresh, vec!["ctrl+r"]),
+            (Action::Help, vec!["f1", "?"]),
+            (Action::Columns, vec!["ctrl+o"]),
+            (Action::History, vec!["ctrl+y"]),
+            (Action::ToggleEnsemble, vec!["ctrl+e"]),
+            (Action::CycleComparison, vec!["ctrl+p"]),
+            (Action::ToggleMountain, vec!["ctrl+t"]),
+            (Action::ToggleBig, vec!["ctrl+b"]),
+            (Action::Tab1, vec!["1", "f2"]),
+            (Action::Tab2, vec!["2", "f3"]),
+            (Action::Tab3, vec!["3", "f4"]),
+            (Action::Tab4, vec!["4", "f5"]),
+            (Action::Tab5, vec!["5", "f6"]),
+            (Action::Tab6, vec!["6", "f7"]),
+            (Action::Tab7, vec!["7", "f8"]),
+            (Action::NextDay, vec!["tab"]),
+            (Action::PreviousDay, vec!["shift+tab"]),
+            (Action::NextHour, vec!["down"]),
+            (Action::PreviousHour, vec!["up"]),
+            (Action::PageDown, vec!["pagedown"]),
+            (Action::PageUp, vec!["pageup"]),
+            (Action::FocusSearch, vec!["/"]),
+            (Action::Search, vec!["enter"]),
+            (Action::LeaveSearch, vec!["esc"]),
+            (Action::ClearSearch, vec!["ctrl+space"]),
+            (Action::Select, vec!["enter"]),
+            (Action::ClosePopup, vec!["esc"]),
+            (Action::Confirm, vec!["enter"]),
+            (Action::ToggleColumn, vec!["space"]),
+        ];
+        let extra: &[(Action, &[&str])] = match self {
+            KeyPreset::Default => &[],
+            KeyPreset::Vim => &[
+                (Action::Quit, &["q"]),
+                (Action::NextDay, &["l"]),
+                (Action::PreviousDay, &["h"]),
+                (Action::NextHour, &["j"]),
+                (Action::PreviousHour, &["k"]),
+                (Action::PageDown, &["ctrl+d"]),
+                (Action::PageUp, &["ctrl+u"]),
+            ],
+            KeyPreset::Emacs => &[
+                (Action::NextDay, &["ctrl+f"]),
+                (Action::PreviousDay, &["ctrl+b"]),
+                (Action::NextHour, &["ctrl+n"]),
+                (Action::PreviousHour, &["ctrl+p"]),
+                (Action::PageDown, &["ctrl+v"]),
+                (Action::PageUp, &["alt+v"]),
+                (Action::LeaveSearch, &["ctrl+g"]),
+                (Action::ClosePopup, &["ctrl+g"]),
+            ],
+        };
+        if self == KeyPreset::Emacs {
+            for (action, keys) in bindings.iter_mut() {
+                match action {
+                    Action::ToggleEnsemble => *keys = vec!["alt+e"],
+                    Action::CycleComparison => *keys = vec!["alt+p"],
+                    Action::ToggleBig => *keys = vec!["alt+b"],
+                    _ => {}
+                }
+            }
+        }
+        for (action, keys) in extra {
+            if let Some((_, bound)) = bindings.iter_mut().find(|(bound, _)| bound == action) {
+                bound.extend(keys.iter());
+            }
+        }
+        bindings
+    }
+}
+
+/// A key with its modifiers, written like `ctrl+r`, `shift+tab`, `f1` or `?`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// Shift is already in the character or in `BackTab`, terminals don't
+    /// agree on also reporting it as a modifier
+    pub fn from_event(key_event: KeyEvent) -> Self {
+        let mut modifiers = key_event.modifiers;
+        if matches!(key_event.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self {
+            code: key_event.code,
+            modifiers,
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let invalid = || format!("Unknown key \"{}\"", text);
+        // A lone `+` is the key itself, not a separator
+        let (modifier_names, key) = match text.strip_suffix("++") {
+            Some(modifier_names) => (modifier_names, "+"),
+            None if text == "+" => ("", "+"),
+            None => text.rsplit_once('+').unwrap_or(("", text)),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(invalid()),
+            };
+        }
+
+        let code = match key.to_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name if name.len() > 1 && name.starts_with('f') => {
+                KeyCode::F(name[1..].parse().map_err(|_| invalid())?)
+            }
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(invalid()),
+                }
+            }
+        };
+        Ok(Self::from_event(KeyEvent::new(code, modifiers)))
+    }
+
+    /// Typed or used for editing by a text box, whatever the preset
+    fn is_text(&self) -> bool {
+        match self.code {
+            KeyCode::Char(_) => !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT),
+            KeyCode::Backspace
+            | KeyCode::Delete
+            | KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Home
+            | KeyCode::End => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Char(' ') => write!(f, "Space"),
+            // Written `Ctrl+C` like the README, though `c` is what's pressed
+            KeyCode::Char(c) if !self.modifiers.is_empty() => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// The active key bindings, a preset with the user's bindings on top
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    bindings: Vec<(Action, Vec<KeyBinding>)>,
+    /// Left to the search bar while typing
+    text_keys: Vec<KeyBinding>,
+}
+
+impl Keymap {
+    /// Fails on unknown keys, on a key bound to two actions listened for at
+    /// the same time and on search bar actions bound to keys it types
+    pub fn new(config: &KeysConfig) -> Result<Self, String> {
+        let mut bindings = config
+            .preset
+            .bindings()
+            .into_iter()
+            .map(|(action, keys)| (action, keys.into_iter().map(String::from).collect()))
+            .collect::<Vec<(Action, Vec<String>)>>();
+        for (action, keys) in bindings.iter_mut() {
+            if let Some(configured) = config.bindings.get(action) {
+                *keys = configured.clone();
+            }
+        }
+
+        let bindings = bindings
+            .into_iter()
+            .map(|(action, keys)| {
+                let keys = keys
+                    .iter()
+                    .map(|key| KeyBinding::parse(key))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((action, keys))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let text_keys = config
+            .preset
+            .text_keys()
+            .iter()
+            .map(|key| KeyBinding::parse(key))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut bound: HashMap<KeyBinding, Action> = HashMap::new();
+        for (action, keys) in &bindings {
+            for key in keys {
+                if action.context() == Context::Search && (key.is_text() || text_keys.contains(key))
+                {
+                    return Err(format!(
+                        "Key {} can't be bound to {}, the search bar types it",
+                        key,
+                        action.name()
+                    ));
+                }
+                if let Some(other) = bound.get(key)
+                    && overlaps(action.context(), other.context())
+                {
+                    return Err(format!(
+                        "Key {} is bound to both {} and {}",
+                        key,
+                        other.name(),
+                        action.name()
+                    ));
+                }
+                bound.insert(*key, *action);
+            }
+        }
+
+        Ok(Self {
+            bindings,
+            text_keys,
+        })
+    }
+
+    /// Keys a focused text box gets before the keymap is consulted
+    pub fn is_text_key(&self, key_event: KeyEvent) -> bool {
+        let key = KeyBinding::from_event(key_event);
+        key.is_text() || self.text_keys.contains(&key)
+    }
+
+    /// The action `key_event` is bound to in `context`, where the focus is
+    pub fn action(&self, key_event: KeyEvent, context: Context) -> Option<Action> {
+        let key = KeyBinding::from_event(key_event);
+        self.bindings
+            .iter()
+            .find(|(action, keys)| keys.contains(&key) && overlaps(action.context(), context))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyBinding] {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == action)
+            .map_or(&[], |(_, keys)| keys)
+    }
+
+    /// The first key bound to `action` for short hints, if any
+    pub fn key(&self, action: Action) -> Option<String> {
+        self.keys(action).first().map(ToString::to_string)
+    }
+}
+
+fn overlaps(a: Context, b: Context) -> bool {
+    a == b || a == Context::Anywhere || b == Context::Anywhere
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn keymap(config: &str) -> Result<Keymap, String> {
+        let config: KeysConfig = toml::from_str(config).map_err(|error| error.to_string())?;
+        Keymap::new(&config)
+    }
+
+    #[test]
+    fn parse_keys() {
+        let parse = |text| KeyBinding::parse(text).unwrap();
+
+        assert_eq!(
+            parse("ctrl+r"),
+            KeyBinding::from_event(key(KeyCode::Char('r'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parse("Ctrl+Alt+Enter"),
+            KeyBinding::from_event(key(
+                KeyCode::Enter,
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+        assert_eq!(
+            parse("shift+tab"),
+            KeyBinding::from_event(key(KeyCode::BackTab, KeyModifiers::SHIFT))
+        );
+        assert_eq!(
+            parse("f12"),
+            KeyBinding::from_event(key(KeyCode::F(12), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parse("ctrl++"),
+            KeyBinding::from_event(key(KeyCode::Char('+'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parse("+"),
+            KeyBinding::from_event(key(KeyCode::Char('+'), KeyModifiers::NONE))
+        );
+        // Terminals report `?` with or without shift
+        assert_eq!(
+            parse("?"),
+            KeyBinding::from_event(key(KeyCode::Char('?'), KeyModifiers::SHIFT))
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_keys() {
+        assert!(KeyBinding::parse("hyper+x").is_err());
+        assert!(KeyBinding::parse("ctrl+xy").is_err());
+        assert!(KeyBinding::parse("fx").is_err());
+    }
+
+    #[test]
+    fn display_matches_the_readme() {
+        assert_eq!(KeyBinding::parse("ctrl+c").unwrap().to_string(), "Ctrl+C");
+        assert_eq!(
+            KeyBinding::parse("shift+tab").unwrap().to_string(),
+            "Shift+Tab"
+        );
+        assert_eq!(
+            KeyBinding::parse("ctrl+space").unwrap().to_string(),
+            "Ctrl+Space"
+        );
+        assert_eq!(KeyBinding::parse("q").unwrap().to_string(), "q");
+    }
+
+    #[test]
+    fn every_preset_is_free_of_conflicts() {
+        for preset in ["default", "vim", "emacs"] {
+            assert!(keymap(&format!("preset = \"{}\"", preset)).is_ok());
+        }
+    }
+
+    #[test]
+    fn conflicts_are_reported() {
+        let error = keymap("[bindings]\nrefresh = [\"ctrl+o\"]").unwrap_err();
+        assert_eq!(error, "Key Ctrl+O is bound to both refresh and columns");
+
+        // Search and forecast keys are never listened for at the same time
+        assert!(keymap("[bindings]\nclear-search = [\"ctrl+l\"]\nselect = [\"ctrl+l\"]").is_ok());
+    }
+
+    #[test]
+    fn keys_the_search_bar_types_are_rejected() {
+        let error = keymap("[bindings]\nclear-search = [\"/\"]").unwrap_err();
+        assert_eq!(
+            error,
+            "Key / can't be bound to clear-search, the search bar types it"
+        );
+        assert!(keymap("[bindings]\nleave-search = [\"left\"]").is_err());
+        assert!(keymap("preset = \"emacs\"\n[bindings]\nsearch = [\"ctrl+f\"]").is_err());
+    }
+
+    #[test]
+    fn bindings_replace_the_preset() {
+        let keymap = keymap("preset = \"vim\"\n[bindings]\nquit = [\"ctrl+q\"]").unwrap();
+
+        assert_eq!(
+            keymap.action(
+                key(KeyCode::Char('q'), KeyModifiers::CONTROL),
+                Context::Forecast
+            ),
+            Some(Action::Quit)
+        );
+        assert_eq!(
+            keymap.action(
+                key(KeyCode::Char('q'), KeyModifiers::NONE),
+                Context::Forecast
+            ),
+            None
+        );
+        assert_eq!(keymap.key(Action::Quit).as_deref(), Some("Ctrl+Q"));
+    }
+
+    #[test]
+    fn actions_follow_the_focus() {
+        let keymap = keymap("").unwrap();
+        let enter = key(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert_eq!(keymap.action(enter, Context::Search), Some(Action::Search));
+        assert_eq!(
+            keymap.action(enter, Context::Forecast),
+            Some(Action::Select)
+        );
+        assert_eq!(keymap.action(enter, Context::Popup), Some(Action::Confirm));
+    }
+
+    #[test]
+    fn popups_share_the_movement_keys() {
+        let keymap = keymap("preset = \"vim\"").unwrap();
+        let popup = |code| keymap.action(key(code, KeyModifiers::NONE), Context::Popup);
+
+        assert_eq!(popup(KeyCode::Char('j')), Some(Action::NextHour));
+        assert_eq!(popup(KeyCode::Up), Some(Action::PreviousHour));
+        assert_eq!(popup(KeyCode::Char('q')), Some(Action::Quit));
+        assert_eq!(popup(KeyCode::Char(' ')), Some(Action::ToggleColumn));
+        assert_eq!(
+            keymap.action(
+                key(KeyCode::Char(' '), KeyModifiers::NONE),
+                Context::Forecast
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn emacs_keeps_editing_keys_in_the_search_bar() {
+        let keymap = keymap("preset = \"emacs\"").unwrap();
+
+        for c in ['f', 'b', 'n', 'p'] {
+            let ctrl = key(KeyCode::Char(c), KeyModifiers::CONTROL);
+            assert!(keymap.is_text_key(ctrl));
+            assert!(keymap.action(ctrl, Context::Forecast).is_some());
+        }
+        assert_eq!(
+            keymap.action(
+                key(KeyCode::Char('f'), KeyModifiers::CONTROL),
+                Context::Forecast
+            ),
+            Some(Action::NextDay)
+        );
+    }
+}
@@ -1,18 +1,298 @@
-use std::rc::Rc;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use serde::{Deserialize, de::IntoDeserializer};
 
-use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
+/// How much room the terminal has, deciding which views fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Below the minimum, nothing useful fits
+    TooSmall,
+    /// A single day line and a trimmed hourly table
+    Compact,
+    /// Days as a vertical list, no details pane
+    Narrow,
+    Wide,
+}
+
+impl Breakpoint {
+    pub const MIN_WIDTH: u16 = 40;
+    pub const MIN_HEIGHT: u16 = 16;
+    /// Below this width the day cards don't fit side by side
+    const NARROW_WIDTH: u16 = 80;
+    /// Below this height a day list leaves no room for the hours
+    const SHORT_HEIGHT: u16 = 20;
+
+    /// Mostly by width, the hourly table takes whatever height is left
+    pub fn for_area(area: Rect) -> Breakpoint {
+        if area.width < Self::MIN_WIDTH || area.height < Self::MIN_HEIGHT {
+            Breakpoint::TooSmall
+        } else if area.width < Self::NARROW_WIDTH || area.height < Self::SHORT_HEIGHT {
+            Breakpoint::Compact
+        } else if area.width < 120 {
+            Breakpoint::Narrow
+        } else {
+            Breakpoint::Wide
+        }
+    }
+
+    /// Width the dashboard is centered in, wide terminals keep a margin
+    pub fn content_width(self, area: Rect) -> u16 {
+        match self {
+            Breakpoint::Wide => (area.width as f32 * 0.8) as u16,
+            _ => area.width,
+        }
+    }
+}
+
+/// A piece of the dashboard that can be placed in the layout
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Panel {
+    Search,
+    /// Location name and elevation
+    Title,
+    /// Current conditions compared with yesterday
+    Now,
+    Warnings,
+    Daily,
+    /// Pollen levels from the air quality API
+    AirQuality,
+    Nowcast,
+    /// The hourly table, or whichever view replaces it
+    Hourly,
+    /// Details of the selected hour
+    Details,
+    Alerts,
+    /// Temperature chart of the selected day
+    Chart,
+    /// Sunrise, sunset and daylight
+    Astronomy,
+    Status,
+}
+
+/// Height of a row or width of a cell: a number of cells, a percentage like
+/// `"30%"`, `"fill"` for a share of what's left, or `"auto"` for what the
+/// panel needs
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(try_from = "RawSize")]
+pub enum Size {
+    Cells(u16),
+    Percent(u16),
+    Fill,
+    #[default]
+    Auto,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawSize {
+    Cells(u16),
+    Named(String),
+}
+
+impl TryFrom<RawSize> for Size {
+    type Error = String;
+
+    fn try_from(raw: RawSize) -> Result<Self, Self::Error> {
+        match raw {
+            RawSize::Cells(cells) => Ok(Size::Cells(cells)),
+            RawSize::Named(name) => match name.as_str() {
+                "fill" => Ok(Size::Fill),
+                "auto" => Ok(Size::Auto),
+                _ => name
+                    .strip_suffix('%')
+                    .and_then(|percent| percent.parse().ok())
+                    .map(Size::Percent)
+                    .ok_or_else(|| {
+                        format!(
+                            "Invalid size {}, expected cells, a percentage, \"fill\" or \"auto\"",
+                            name
+                        )
+                    }),
+            },
+        }
+    }
+}
+
+/// One panel, or several stacked top to bottom
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(try_from = "RawCell")]
+pub enum LayoutCell {
+    Panel(Panel),
+    Stack(Vec<Panel>),
+}
 
-pub fn default_layout(area: Rect) -> Rc<[Rect]> {
-    Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(6),
-            Constraint::Fill(1),
-            Constraint::Length(1),
-        ])
-        .split(area)
+/// Panel names are parsed by hand, an untagged enum would hide which name is
+/// wrong
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawCell {
+    Panel(String),
+    Stack(Vec<String>),
+}
+
+impl TryFrom<RawCell> for LayoutCell {
+    type Error = String;
+
+    fn try_from(raw: RawCell) -> Result<Self, Self::Error> {
+        let panel = |name: &str| {
+            Panel::deserialize(name.into_deserializer())
+                .map_err(|err: serde::de::value::Error| format!("Invalid panel: {}", err))
+        };
+        match raw {
+            RawCell::Panel(name) => panel(&name).map(LayoutCell::Panel),
+            RawCell::Stack(names) => names
+                .iter()
+                .map(|name| panel(name))
+                .collect::<Result<_, _>>()
+                .map(LayoutCell::Stack),
+        }
+    }
+}
+
+impl LayoutCell {
+    fn panels(&self) -> &[Panel] {
+        match self {
+            LayoutCell::Panel(panel) => std::slice::from_ref(panel),
+            LayoutCell::Stack(panels) => panels,
+        }
+    }
+}
+
+/// A row of the dashboard, its cells side by side
+#[derive(Debug, Deserialize, Clone)]
+pub struct LayoutRow {
+    pub panels: Vec<LayoutCell>,
+    #[serde(default)]
+    pub height: Size,
+    /// Width of each cell, cells without one share the leftover width
+    #[serde(default)]
+    pub widths: Vec<Size>,
+}
+
+impl LayoutRow {
+    fn new(panels: Vec<LayoutCell>, height: Size, widths: Vec<Size>) -> Self {
+        Self {
+            panels,
+            height,
+            widths,
+        }
+    }
+}
+
+/// The built-in dashboard, used when the config has no `[[layout]]` rows
+pub fn default_rows() -> Vec<LayoutRow> {
+    let row = |panel| LayoutRow::new(vec![LayoutCell::Panel(panel)], Size::Auto, Vec::new());
+    vec![
+        row(Panel::Search),
+        row(Panel::Title),
+        row(Panel::Now),
+        row(Panel::Warnings),
+        row(Panel::Daily),
+        row(Panel::AirQuality),
+        row(Panel::Nowcast),
+        LayoutRow::new(
+            vec![
+                LayoutCell::Panel(Panel::Hourly),
+                LayoutCell::Stack(vec![Panel::Details, Panel::Alerts]),
+            ],
+            Size::Fill,
+            vec![Size::Fill, Size::Cells(32)],
+        ),
+        row(Panel::Status),
+    ]
+}
+
+fn constraint(size: Size, auto: Constraint) -> Constraint {
+    match size {
+        Size::Cells(cells) => Constraint::Length(cells),
+        Size::Percent(percent) => Constraint::Percentage(percent),
+        Size::Fill => Constraint::Fill(1),
+        Size::Auto => auto,
+    }
+}
+
+/// Place every panel of `rows` in `area`. `auto` gives the height a panel
+/// needs, rows of several cells only keep their first one below the wide
+/// breakpoint, and rows other than search and status are centered in the
+/// breakpoint's content width.
+pub fn dashboard_layout(
+    area: Rect,
+    rows: &[LayoutRow],
+    breakpoint: Breakpoint,
+    auto: impl Fn(Panel) -> Constraint,
+) -> Vec<(Panel, Rect)> {
+    // Stacked panels add up, side by side the tallest wins
+    let stack_height = |panels: &[Panel]| {
+        panels
+            .iter()
+            .map(|&panel| auto(panel))
+            .try_fold(0, |total, constraint| match constraint {
+                Constraint::Length(height) => Some(total + height),
+                _ => None,
+            })
+    };
+    let row_height = |row: &LayoutRow| {
+        let auto = row
+            .panels
+            .iter()
+            .map(|cell| stack_height(cell.panels()))
+            .try_fold(0, |tallest, height| Some(tallest.max(height?)))
+            .map_or(Constraint::Fill(1), Constraint::Length);
+        constraint(row.height, auto)
+    };
+
+    let row_areas = Layout::vertical(rows.iter().map(row_height)).split(area);
+
+    let mut placed = Vec::new();
+    for (row, &row_area) in rows.iter().zip(row_areas.iter()) {
+        let full_width = matches!(
+            row.panels.as_slice(),
+            [LayoutCell::Panel(Panel::Search | Panel::Status)]
+        );
+        let row_area = if full_width {
+            row_area
+        } else {
+            center(row_area, breakpoint.content_width(row_area))
+        };
+
+        let cells = if breakpoint == Breakpoint::Wide {
+            &row.panels[..]
+        } else {
+            &row.panels[..row.panels.len().min(1)]
+        };
+        let widths = (0..cells.len()).map(|i| {
+            row.widths
+                .get(i)
+                .filter(|_| cells.len() > 1)
+                .map_or(Constraint::Fill(1), |&width| {
+                    constraint(width, Constraint::Fill(1))
+                })
+        });
+        let cell_areas = Layout::horizontal(widths).spacing(1).split(row_area);
+
+        for (cell, &cell_area) in cells.iter().zip(cell_areas.iter()) {
+            let panels = cell.panels();
+            let panel_areas =
+                Layout::vertical(panels.iter().map(|&panel| auto(panel))).split(cell_area);
+            placed.extend(panels.iter().copied().zip(panel_areas.iter().copied()));
+        }
+    }
+    placed
+}
+
+/// The tab bar above the selected tab
+pub fn tabbed_layout(area: Rect) -> [Rect; 2] {
+    Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area)
+}
+
+/// Search, a full page view and the status line
+pub fn page_layout(area: Rect) -> [Rect; 3] {
+    Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Fill(1),
+        Constraint::Length(1),
+    ])
+    .areas(area)
 }
 
 pub fn center(area: Rect, width: u16) -> Rect {
@@ -21,3 +301,83 @@ pub fn center(area: Rect, width: u16) -> Rect {
         .areas(area);
     area
 }
+
+pub fn popup(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    center(area, width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(width: u16, height: u16) -> Rect {
+        Rect::new(0, 0, width, height)
+    }
+
+    #[test]
+    fn breakpoints_follow_the_width() {
+        assert_eq!(Breakpoint::for_area(area(39, 50)), Breakpoint::TooSmall);
+        assert_eq!(Breakpoint::for_area(area(200, 15)), Breakpoint::TooSmall);
+        assert_eq!(Breakpoint::for_area(area(79, 50)), Breakpoint::Compact);
+        assert_eq!(Breakpoint::for_area(area(200, 19)), Breakpoint::Compact);
+        // A common 80x24 or 120x24 terminal isn't squeezed to one day
+        assert_eq!(Breakpoint::for_area(area(80, 24)), Breakpoint::Narrow);
+        assert_eq!(Breakpoint::for_area(area(120, 24)), Breakpoint::Wide);
+    }
+
+    #[test]
+    fn sizes_parse_from_config() {
+        let size = |toml: &str| {
+            #[derive(Deserialize)]
+            struct Row {
+                height: Size,
+            }
+            toml::from_str::<Row>(toml).map(|row| row.height)
+        };
+        assert_eq!(size("height = 5").unwrap(), Size::Cells(5));
+        assert_eq!(size("height = \"30%\"").unwrap(), Size::Percent(30));
+        assert_eq!(size("height = \"fill\"").unwrap(), Size::Fill);
+        assert!(size("height = \"tall\"").is_err());
+    }
+
+    #[test]
+    fn default_dashboard_sizing() {
+        let auto = |panel| match panel {
+            Panel::Search => Constraint::Length(3),
+            Panel::Hourly => Constraint::Fill(1),
+            Panel::Details => Constraint::Length(10),
+            _ => Constraint::Length(1),
+        };
+        let placed = |width, breakpoint| {
+            dashboard_layout(area(width, 40), &default_rows(), breakpoint, auto)
+        };
+        let find = |placed: &[(Panel, Rect)], panel| {
+            placed
+                .iter()
+                .find(|(placed, _)| *placed == panel)
+                .map(|(_, area)| *area)
+        };
+
+        let wide = placed(150, Breakpoint::Wide);
+        let search = find(&wide, Panel::Search).unwrap();
+        let hourly = find(&wide, Panel::Hourly).unwrap();
+        let details = find(&wide, Panel::Details).unwrap();
+        // Search spans the terminal, the rest sits in the content width
+        assert_eq!(search.width, 150);
+        assert_eq!(hourly.width + 1 + details.width, 120);
+        assert_eq!(details.width, 32);
+        assert_eq!(details.height, 10);
+        // Auto rows take what they need, the hourly row fills the rest
+        assert_eq!(hourly.y, 3 + 6);
+        assert_eq!(hourly.height, 40 - 3 - 6 - 1);
+
+        // Narrower, the details beside the table are dropped
+        let narrow = placed(100, Breakpoint::Narrow);
+        assert_eq!(find(&narrow, Panel::Hourly).unwrap().width, 100);
+        assert!(find(&narrow, Panel::Details).is_none());
+        assert!(find(&narrow, Panel::Alerts).is_none());
+    }
+}
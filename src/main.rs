@@ -1,19 +1,59 @@
 mod app;
+mod cli;
+mod config;
 mod data;
+mod gradient;
+mod icons;
+mod keymap;
 mod layout;
+mod notifier;
+mod theme;
 mod weather_service;
 mod widgets;
 
 use app::App;
-use std::error::Error;
+use clap::Parser;
+use cli::Cli;
+use config::Config;
+use gradient::TemperatureGradient;
+use keymap::Keymap;
+use notifier::DesktopNotifier;
+use ratatui::crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+};
+use std::{error::Error, io::stdout, panic, sync::Arc};
+use theme::Theme;
 use tokio::sync::mpsc;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    let mut config = Config::load()?;
+    cli.apply(&mut config);
+    let theme = Theme::load(config.theme.as_deref().unwrap_or("dark"))?;
+    let gradient = TemperatureGradient::new(&config.temperature_colors, &theme)?;
+    let keymap = Keymap::new(&config.keys)?;
     let mut terminal = ratatui::init();
+    // Runs before ratatui's own hook, which restores the rest of the terminal
+    let restore_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = execute!(stdout(), DisableMouseCapture);
+        restore_hook(info);
+    }));
+    if let Err(err) = execute!(stdout(), EnableMouseCapture) {
+        ratatui::restore();
+        return Err(err.into());
+    }
     let (tx, mut rx) = mpsc::channel(1);
-    let mut app = App::new(tx);
+    let notifications = config.notifications.enabled;
+    let mut app = App::new(tx, config, theme, gradient, keymap);
+    if notifications {
+        app = app.notifier(Arc::new(DesktopNotifier));
+    }
     let app_result = app.run(&mut terminal, &mut rx).await;
+    let mouse_result = execute!(stdout(), DisableMouseCapture);
     ratatui::restore();
-    app_result
+    app_result?;
+    Ok(mouse_result?)
 }
@@ -0,0 +1,320 @@
+use std::error::Error;
+
+use chrono::{NaiveDateTime, TimeDelta, Timelike};
+
+use crate::{config::NotificationConfig, data::alert_rules::AlertSpan, data::weather::Weather};
+
+/// Something that can put a message in front of the user outside the TUI.
+/// The desktop implementation talks to the freedesktop notification service
+/// over D-Bus, anything else (e.g. a stub that records calls) can stand in.
+pub trait Notifier: Send + Sync {
+    fn notify(&self, summary: &str, body: &str) -> Result<(), Box<dyn Error + Send + Sync>>;
+}
+
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn notify(&self, summary: &str, body: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        notify_rust::Notification::new()
+            .appname("weather")
+            .summary(summary)
+            .body(body)
+            .show()?;
+        Ok(())
+    }
+}
+
+pub struct Announcement {
+    /// What the event is, e.g. `rain` or `alert:Freezing`. Together with the
+    /// span it identifies the event across refreshes, so it is only announced
+    /// once even when the forecast moves it by an hour or two.
+    pub key: String,
+    pub start: NaiveDateTime,
+    /// Start of the last hour of the event
+    pub end: NaiveDateTime,
+    pub summary: String,
+    pub body: String,
+}
+
+/// Events worth announcing for the forecast at `now`: rain starting within
+/// the configured window and any alert rule matches that haven't ended.
+pub fn upcoming_events(
+    config: &NotificationConfig,
+    hourly: &[Weather],
+    alerts: &[AlertSpan],
+    now: NaiveDateTime,
+) -> Vec<Announcement> {
+    let mut events = Vec::new();
+    let current_hour = now.date().and_hms_opt(now.hour(), 0, 0).unwrap_or(now);
+    let window_end = current_hour + TimeDelta::hours(config.rain_within_hours as i64);
+
+    let upcoming: Vec<&Weather> = hourly
+        .iter()
+        .filter(|weather| weather.date_time >= current_hour)
+        .collect();
+    let is_wet = |weather: &&Weather| {
+        weather
+            .precip
+            .is_some_and(|precip| precip >= config.rain_probability)
+    };
+    let is_dry = |weather: &&Weather| {
+        weather
+            .precip
+            .is_some_and(|precip| precip < config.rain_probability)
+    };
+
+    // Only announce a change from dry to wet, not rain that is already falling
+    if upcoming.first().is_some_and(is_dry)
+        && let Some(start) = upcoming
+            .iter()
+            .position(|weather| weather.date_time <= window_end && is_wet(weather))
+    {
+        let rain = upcoming[start];
+        let end = upcoming[start..]
+            .iter()
+            .take_while(|weather| is_wet(weather))
+            .last()
+            .map_or(rain.date_time, |weather| weather.date_time);
+        events.push(Announcement {
+            key: "rain".to_string(),
+            start: rain.date_time,
+            end,
+            summary: "Rain on the way".to_string(),
+            body: format!(
+                "{}% chance of rain from {}",
+                rain.precip.unwrap_or_default(),
+                rain.date_time.format("%-I %p")
+            ),
+        });
+    }
+
+    if config.alerts {
+        events.extend(
+            alerts
+                .iter()
+                .filter(|alert| alert.end >= current_hour)
+                .map(|alert| Announcement {
+                    key: format!("alert:{}", alert.name),
+                    start: alert.start,
+                    end: alert.end,
+                    summary: format!("Weather alert: {}", alert.name),
+                    body: format!("From {}", alert.start.format("%a %-I %p")),
+                }),
+        );
+    }
+
+    events
+}
+
+/// An event that was announced and the hours it last covered
+struct Announced {
+    key: String,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+}
+
+impl Announced {
+    /// Back to back hours count as the same event
+    fn overlaps(&self, event: &Announcement) -> bool {
+        event.key == self.key
+            && event.start <= self.end + TimeDelta::hours(1)
+            && event.end + TimeDelta::hours(1) >= self.start
+    }
+}
+
+/// Remembers which events were already announced so a refresh that brings
+/// the same forecast doesn't notify again. An event is forgotten once it is
+/// over, so the next one of the same kind is announced.
+#[derive(Default)]
+pub struct NotificationTracker {
+    announced: Vec<Announced>,
+}
+
+impl NotificationTracker {
+    pub fn unannounced(
+        &mut self,
+        events: Vec<Announcement>,
+        now: NaiveDateTime,
+    ) -> Vec<Announcement> {
+        self.announced
+            .retain(|announced| announced.end + TimeDelta::hours(1) > now);
+
+        events
+            .into_iter()
+            .filter(|event| {
+                match self
+                    .announced
+                    .iter_mut()
+                    .find(|announced| announced.overlaps(event))
+                {
+                    // Follow the event as the forecast moves it
+                    Some(announced) => {
+                        announced.start = event.start;
+                        announced.end = event.end;
+                        false
+                    }
+                    None => {
+                        self.announced.push(Announced {
+                            key: event.key.clone(),
+                            start: event.start,
+                            end: event.end,
+                        });
+                        true
+                    }
+                }
+            })
+            .collect()
+    }
+
+    pub fn reset(&mut self) {
+        self.announced.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Keeps every notification instead of showing it
+    #[derive(Default)]
+    struct RecordingNotifier {
+        sent: Mutex<Vec<String>>,
+    }
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&self, summary: &str, body: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+            self.sent
+                .lock()
+                .unwrap()
+                .push(format!("{}: {}", summary, body));
+            Ok(())
+        }
+    }
+
+    fn time(hour: u32) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2024-07-04T00:00", "%Y-%m-%dT%H:%M").unwrap()
+            + TimeDelta::hours(hour as i64)
+    }
+
+    /// One hour per precipitation probability, starting at midnight
+    fn hourly(precip: &[u16]) -> Vec<Weather> {
+        precip
+            .iter()
+            .enumerate()
+            .map(|(hour, &precip)| Weather {
+                date_time: time(hour as u32),
+                precip: Some(precip),
+                ..Weather::default()
+            })
+            .collect()
+    }
+
+    /// What a refresh at `now` would notify
+    fn refresh(
+        tracker: &mut NotificationTracker,
+        notifier: &dyn Notifier,
+        hourly: &[Weather],
+        alerts: &[AlertSpan],
+        now: NaiveDateTime,
+    ) {
+        let events = upcoming_events(&NotificationConfig::default(), hourly, alerts, now);
+        for event in tracker.unannounced(events, now) {
+            notifier.notify(&event.summary, &event.body).unwrap();
+        }
+    }
+
+    #[test]
+    fn rain_starting_within_window() {
+        let events = upcoming_events(
+            &NotificationConfig::default(),
+            &hourly(&[10, 20, 60, 80, 10]),
+            &[],
+            time(0),
+        );
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].key, "rain");
+        assert_eq!((events[0].start, events[0].end), (time(2), time(3)));
+        assert_eq!(events[0].body, "60% chance of rain from 2 AM");
+    }
+
+    #[test]
+    fn no_rain_announced_when_already_raining_or_too_far_off() {
+        let config = NotificationConfig::default();
+        assert!(upcoming_events(&config, &hourly(&[70, 80, 10]), &[], time(0)).is_empty());
+        assert!(upcoming_events(&config, &hourly(&[10, 10, 10, 90]), &[], time(0)).is_empty());
+    }
+
+    #[test]
+    fn unknown_chance_of_rain_is_not_dry() {
+        let mut hours = hourly(&[0, 90]);
+        hours[0].precip = None;
+        assert!(upcoming_events(&NotificationConfig::default(), &hours, &[], time(0)).is_empty());
+    }
+
+    #[test]
+    fn shifted_forecast_is_announced_once() {
+        let notifier = RecordingNotifier::default();
+        let mut tracker = NotificationTracker::default();
+
+        refresh(
+            &mut tracker,
+            &notifier,
+            &hourly(&[10, 10, 60, 60, 10, 10]),
+            &[],
+            time(0),
+        );
+        // The next run moves the rain an hour later
+        refresh(
+            &mut tracker,
+            &notifier,
+            &hourly(&[10, 10, 10, 60, 60, 10]),
+            &[],
+            time(1),
+        );
+
+        assert_eq!(
+            *notifier.sent.lock().unwrap(),
+            ["Rain on the way: 60% chance of rain from 2 AM"]
+        );
+    }
+
+    #[test]
+    fn event_is_announced_again_after_it_ends() {
+        let notifier = RecordingNotifier::default();
+        let mut tracker = NotificationTracker::default();
+        let rain = hourly(&[10, 60, 10, 10, 10, 10, 60, 10]);
+
+        refresh(&mut tracker, &notifier, &rain, &[], time(0));
+        refresh(&mut tracker, &notifier, &rain, &[], time(5));
+
+        assert_eq!(notifier.sent.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn alerts_keyed_by_rule_and_span() {
+        let notifier = RecordingNotifier::default();
+        let mut tracker = NotificationTracker::default();
+        let span = |start, end| AlertSpan {
+            name: "Freezing".to_string(),
+            start: time(start),
+            end: time(end),
+        };
+        let dry = hourly(&[0; 24]);
+
+        refresh(&mut tracker, &notifier, &dry, &[span(3, 5)], time(0));
+        refresh(&mut tracker, &notifier, &dry, &[span(4, 6)], time(0));
+        // A separate night of frost is a new event
+        refresh(&mut tracker, &notifier, &dry, &[span(20, 22)], time(1));
+
+        assert_eq!(
+            *notifier.sent.lock().unwrap(),
+            [
+                "Weather alert: Freezing: From Thu 3 AM",
+                "Weather alert: Freezing: From Thu 8 PM"
+            ]
+        );
+    }
+}
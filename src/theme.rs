@@ -0,0 +1,237 @@
+use std::{error::Error, fs};
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+use crate::config::Config;
+
+/// Colors used across the UI, by role rather than by widget. Any color
+/// ratatui understands works in a theme file: names (`light-blue`), 256
+/// color indexes (`208`) or hex (`#e69f00`).
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct Theme {
+    /// Selected day, hour and list entries
+    pub accent: Color,
+    /// Secondary text, past days, missing values
+    pub muted: Color,
+    /// Above normal, warmer than yesterday
+    pub warm: Color,
+    /// Below normal, cooler than yesterday, freezing
+    pub cold: Color,
+    /// Threshold alert matches
+    pub alert: Color,
+    pub error: Color,
+    /// Rising pressure
+    pub good: Color,
+    /// Falling pressure, model spread, ensemble median
+    pub caution: Color,
+    /// Precipitation bars and nowcast
+    pub precip: Color,
+    pub loader: Color,
+    /// Shaded ensemble range
+    pub band: Color,
+    pub status_bg: Color,
+    pub status_fg: Color,
+    pub current_hour_bg: Color,
+    pub current_hour_fg: Color,
+    /// Text on the official warning banner
+    pub banner_fg: Color,
+    /// Minor warnings
+    pub info: Color,
+    /// Low to very high, for pollen levels and warning severity
+    pub severity: [Color; 4],
+    /// Temperature scale from cold to hot, used unless the config sets its
+    /// own stops
+    pub temperature: [Color; 7],
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub const BUILT_IN: [&str; 4] = ["dark", "light", "high-contrast", "colorblind"];
+
+    pub fn dark() -> Self {
+        Self {
+            accent: Color::LightBlue,
+            muted: Color::DarkGray,
+            warm: Color::LightRed,
+            cold: Color::LightBlue,
+            alert: Color::LightRed,
+            error: Color::LightRed,
+            good: Color::Green,
+            caution: Color::Yellow,
+            precip: Color::Blue,
+            loader: Color::Cyan,
+            band: Color::DarkGray,
+            status_bg: Color::DarkGray,
+            status_fg: Color::White,
+            current_hour_bg: Color::DarkGray,
+            current_hour_fg: Color::White,
+            banner_fg: Color::Black,
+            info: Color::Blue,
+            severity: [Color::Green, Color::Yellow, Color::LightRed, Color::Red],
+            temperature: [
+                Color::Rgb(94, 79, 162),
+                Color::Rgb(50, 136, 189),
+                Color::Rgb(102, 194, 165),
+                Color::Rgb(230, 245, 152),
+                Color::Rgb(253, 174, 97),
+                Color::Rgb(213, 62, 79),
+                Color::Rgb(158, 1, 66),
+            ],
+        }
+    }
+
+    /// For terminals with a light background, where the light colors of the
+    /// dark theme wash out
+    pub fn light() -> Self {
+        Self {
+            accent: Color::Blue,
+            muted: Color::Gray,
+            warm: Color::Red,
+            cold: Color::Blue,
+            alert: Color::Red,
+            error: Color::Red,
+            good: Color::Green,
+            caution: Color::Magenta,
+            precip: Color::Blue,
+            loader: Color::Blue,
+            band: Color::Indexed(252),
+            status_bg: Color::Indexed(252),
+            status_fg: Color::Black,
+            current_hour_bg: Color::Indexed(254),
+            current_hour_fg: Color::Black,
+            banner_fg: Color::White,
+            info: Color::Blue,
+            severity: [
+                Color::Green,
+                Color::Indexed(136),
+                Color::Red,
+                Color::Magenta,
+            ],
+            // Pale yellows and greens vanish on white, keep every stop dark
+            temperature: [
+                Color::Rgb(84, 39, 143),
+                Color::Rgb(33, 102, 172),
+                Color::Rgb(1, 133, 113),
+                Color::Rgb(128, 128, 0),
+                Color::Rgb(217, 95, 2),
+                Color::Rgb(203, 24, 29),
+                Color::Rgb(103, 0, 13),
+            ],
+        }
+    }
+
+    /// Bright colors only, nothing dimmed
+    pub fn high_contrast() -> Self {
+        Self {
+            accent: Color::LightCyan,
+            muted: Color::White,
+            warm: Color::LightRed,
+            cold: Color::LightCyan,
+            alert: Color::LightMagenta,
+            error: Color::LightRed,
+            good: Color::LightGreen,
+            caution: Color::LightYellow,
+            precip: Color::LightCyan,
+            loader: Color::White,
+            band: Color::Gray,
+            status_bg: Color::White,
+            status_fg: Color::Black,
+            current_hour_bg: Color::White,
+            current_hour_fg: Color::Black,
+            banner_fg: Color::Black,
+            info: Color::LightCyan,
+            severity: [
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::LightRed,
+                Color::LightMagenta,
+            ],
+            temperature: [
+                Color::Rgb(135, 135, 255),
+                Color::Rgb(0, 175, 255),
+                Color::Rgb(0, 255, 175),
+                Color::Rgb(255, 255, 95),
+                Color::Rgb(255, 175, 0),
+                Color::Rgb(255, 95, 95),
+                Color::Rgb(255, 95, 255),
+            ],
+        }
+    }
+
+    /// The Okabe-Ito palette, distinguishable with the common forms of color
+    /// blindness. Warm and cold are orange and blue rather than red and green.
+    pub fn colorblind() -> Self {
+        let orange = Color::Rgb(230, 159, 0);
+        let sky_blue = Color::Rgb(86, 180, 233);
+        let bluish_green = Color::Rgb(0, 158, 115);
+        let yellow = Color::Rgb(240, 228, 66);
+        let blue = Color::Rgb(0, 114, 178);
+        let vermillion = Color::Rgb(213, 94, 0);
+        let reddish_purple = Color::Rgb(204, 121, 167);
+
+        Self {
+            accent: sky_blue,
+            muted: Color::DarkGray,
+            warm: vermillion,
+            cold: sky_blue,
+            alert: reddish_purple,
+            error: vermillion,
+            good: bluish_green,
+            caution: yellow,
+            precip: blue,
+            loader: sky_blue,
+            band: Color::DarkGray,
+            status_bg: Color::DarkGray,
+            status_fg: Color::White,
+            current_hour_bg: Color::DarkGray,
+            current_hour_fg: Color::White,
+            banner_fg: Color::Black,
+            info: sky_blue,
+            severity: [bluish_green, yellow, orange, vermillion],
+            temperature: [
+                blue,
+                sky_blue,
+                bluish_green,
+                yellow,
+                orange,
+                vermillion,
+                reddish_purple,
+            ],
+        }
+    }
+
+    /// A built-in theme by name, or a user theme from
+    /// `<config dir>/weather/themes/<name>.toml`. Colors a user theme leaves
+    /// out come from the dark theme.
+    pub fn load(name: &str) -> Result<Theme, Box<dyn Error>> {
+        match name {
+            "dark" => return Ok(Self::dark()),
+            "light" => return Ok(Self::light()),
+            "high-contrast" => return Ok(Self::high_contrast()),
+            "colorblind" => return Ok(Self::colorblind()),
+            _ => {}
+        }
+
+        let path = Config::path()
+            .and_then(|path| path.parent().map(|dir| dir.join("themes")))
+            .map(|dir| dir.join(format!("{}.toml", name)))
+            .filter(|path| path.exists())
+            .ok_or_else(|| {
+                format!(
+                    "Unknown theme {}, expected one of {} or a file in the themes directory",
+                    name,
+                    Self::BUILT_IN.join(", ")
+                )
+            })?;
+        let contents = fs::read_to_string(&path)?;
+        toml::from_str(&contents)
+            .map_err(|err| format!("Invalid theme {}: {}", path.display(), err).into())
+    }
+}
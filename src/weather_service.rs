@@ -1,23 +1,116 @@
 use std::error::Error;
 
-use crate::data::location::geocode;
+use chrono::NaiveDate;
+
+use crate::config::Config;
+use crate::data::cap::{CapAlert, fetch_warnings};
+use crate::data::climate::Climatology;
+use crate::data::ensemble::{EnsembleSpread, fetch_ensemble};
+use crate::data::location::{Location, geocode};
+use crate::data::marine::{OpenMeteoMarine, fetch_marine};
+use crate::data::models::{ModelComparison, fetch_comparison};
+use crate::data::weather::OpenMeteoPollen;
 use crate::data::weather::OpenMeteoResponse;
-use crate::data::weather::fetch_weather;
+use crate::data::weather::{fetch_history, fetch_pollen, fetch_weather};
 
+#[derive(Clone)]
 pub struct WeatherData {
     pub weather: OpenMeteoResponse,
-    pub location_name: String,
+    pub pollen: OpenMeteoPollen,
+    pub warnings: Vec<CapAlert>,
+    pub location: Location,
+    /// Set when the data is from the archive rather than the forecast
+    pub history: Option<(NaiveDate, NaiveDate)>,
+    /// Climate normals, filled in after the forecast since the first fetch
+    /// for a location is slow
+    pub climate: Option<Climatology>,
+    /// Only fetched when the ensemble mode is enabled
+    pub ensemble: Option<EnsembleSpread>,
+    /// Only fetched when models to compare are configured
+    pub comparison: Option<ModelComparison>,
+    /// Present for coastal locations
+    pub marine: Option<OpenMeteoMarine>,
+}
+
+pub async fn dispatch_weather(
+    query: &str,
+    config: &Config,
+) -> Result<WeatherData, Box<dyn Error + Send + Sync>> {
+    dispatch_forecast(geocode(query).await?, config).await
 }
 
-pub async fn dispatch_weather(query: &str) -> Result<WeatherData, Box<dyn Error + Send + Sync>> {
-    let (name, geocode) = geocode(query).await?;
+/// The forecast for an already geocoded location
+pub async fn dispatch_forecast(
+    location: Location,
+    config: &Config,
+) -> Result<WeatherData, Box<dyn Error + Send + Sync>> {
+    let (lat, lon) = location.coordinates;
+
+    // A surveyed summit elevation beats the coarse terrain model's guess
+    let elevation = config.forecast.elevation.or(location.elevation);
+    // The sources don't depend on each other, so none waits on another
+    let (weather, pollen, warnings, ensemble, marine, comparison) = tokio::join!(
+        fetch_weather(lat, lon, elevation, &config.forecast),
+        fetch_pollen(lat, lon),
+        fetch_warnings(
+            &config.warnings,
+            lat,
+            lon,
+            &location.name,
+            location.country_code.as_deref(),
+        ),
+        async {
+            if config.ensemble.enabled {
+                fetch_ensemble(&config.ensemble, lat, lon).await.ok()
+            } else {
+                None
+            }
+        },
+        fetch_marine(lat, lon, config.forecast.past_days),
+        async {
+            if config.forecast.compare_models.is_empty() {
+                None
+            } else {
+                fetch_comparison(&config.forecast, lat, lon).await.ok()
+            }
+        },
+    );
+    let weather = weather?;
+    // Pollen is a nice-to-have, a failed lookup shouldn't hide the forecast
+    let pollen = pollen.unwrap_or_default();
+    // Most places aren't on the coast, treat a failed lookup the same way
+    let marine = marine.ok().flatten();
 
-    let (lat, lon) = geocode.first().cloned().unwrap();
+    Ok(WeatherData {
+        weather,
+        pollen,
+        warnings,
+        location,
+        history: None,
+        climate: None,
+        ensemble,
+        comparison,
+        marine,
+    })
+}
 
-    let weather = fetch_weather(lat, lon).await?;
+pub async fn dispatch_history(
+    location: Location,
+    (start, end): (NaiveDate, NaiveDate),
+    archive_url: &str,
+) -> Result<WeatherData, Box<dyn Error + Send + Sync>> {
+    let (lat, lon) = location.coordinates;
+    let weather = fetch_history(archive_url, lat, lon, start, end).await?;
 
     Ok(WeatherData {
         weather,
-        location_name: name,
+        pollen: OpenMeteoPollen::default(),
+        warnings: Vec::new(),
+        location,
+        history: Some((start, end)),
+        climate: None,
+        ensemble: None,
+        comparison: None,
+        marine: None,
     })
 }
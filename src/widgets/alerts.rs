@@ -0,0 +1,89 @@
+use chrono::Local;
+use ratatui::{
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget, Wrap},
+};
+
+use crate::data::alert_rules::AlertSpan;
+use crate::{icons::IconSet, theme::Theme};
+
+#[derive(Default)]
+pub struct AlertList {
+    data: Vec<AlertSpan>,
+    theme: Theme,
+    icons: IconSet,
+}
+
+impl Widget for AlertList {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let now = Local::now().naive_local();
+        let lines: Vec<Line> = self
+            .data
+            .iter()
+            .filter(|span| span.end >= now - chrono::TimeDelta::hours(1))
+            .map(|span| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", self.icons.warning()),
+                        Style::new().fg(self.theme.alert),
+                    ),
+                    Span::raw(span.name.clone()).bold(),
+                    Span::raw(format!(" {}", Self::format_span(span))),
+                ])
+            })
+            .collect();
+
+        let block = Block::bordered().title(format!("Alerts ({})", lines.len()));
+        if lines.is_empty() {
+            Paragraph::new("No alerts")
+                .style(Style::new().fg(self.theme.muted))
+                .block(block)
+                .render(area, buf);
+            return;
+        }
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .render(area, buf);
+    }
+}
+
+impl AlertList {
+    pub fn new(alerts: Vec<AlertSpan>) -> Self {
+        Self {
+            data: alerts,
+            theme: Theme::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    fn format_span(span: &AlertSpan) -> String {
+        if span.start == span.end {
+            span.start.format("%a %-I %p").to_string()
+        } else if span.start.date() == span.end.date() {
+            format!(
+                "{}–{}",
+                span.start.format("%a %-I %p"),
+                span.end.format("%-I %p")
+            )
+        } else {
+            format!(
+                "{} – {}",
+                span.start.format("%a %-I %p"),
+                span.end.format("%a %-I %p")
+            )
+        }
+    }
+}
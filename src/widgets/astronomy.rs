@@ -0,0 +1,60 @@
+use ratatui::{
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::data::weather::Astronomy;
+use crate::theme::Theme;
+
+/// Sunrise, sunset and daylight of the selected day on one line
+#[derive(Default)]
+pub struct AstronomyStrip {
+    data: Option<Astronomy>,
+    theme: Theme,
+}
+
+impl Widget for AstronomyStrip {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let Some(astronomy) = self.data else {
+            return;
+        };
+
+        let daylight = astronomy.daylight as i64 / 60;
+        let mut spans = vec![
+            Span::raw("Sun").bold(),
+            Span::raw(format!(
+                "  Rise {}  Set {}  {}h {}m daylight",
+                astronomy.sunrise.format("%-I:%M %p"),
+                astronomy.sunset.format("%-I:%M %p"),
+                daylight / 60,
+                daylight % 60
+            )),
+        ];
+        if let Some(change) = astronomy.change {
+            let minutes = (change / 60.0).round();
+            spans.push(Span::styled(
+                format!(" ({:+.0} min)", minutes),
+                Style::new().fg(self.theme.muted),
+            ));
+        }
+
+        Paragraph::new(Line::from(spans))
+            .centered()
+            .render(area, buf);
+    }
+}
+
+impl AstronomyStrip {
+    pub fn new(astronomy: Option<Astronomy>) -> Self {
+        Self {
+            data: astronomy,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
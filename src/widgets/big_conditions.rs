@@ -0,0 +1,279 @@
+use ratatui::{
+    layout::{Constraint, Flex, Layout},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::data::{
+    nowcast::Nowcast,
+    weather::{OpenMeteoCurrent, get_weather_description},
+};
+use crate::{gradient::TemperatureGradient, icons::IconSet, theme::Theme};
+
+const ART_WIDTH: u16 = 13;
+const DIGIT_HEIGHT: u16 = 5;
+
+/// Current conditions as ASCII art and large digits, readable from across
+/// the room
+#[derive(Default)]
+pub struct BigConditions {
+    location: String,
+    data: Option<OpenMeteoCurrent>,
+    /// Today's high and low
+    today: Option<(f32, f32)>,
+    nowcast: Option<Nowcast>,
+    theme: Theme,
+    icons: IconSet,
+    gradient: TemperatureGradient,
+}
+
+impl Widget for BigConditions {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let Some(current) = &self.data else {
+            return;
+        };
+
+        let [
+            location_area,
+            _,
+            big_area,
+            _,
+            desc_area,
+            feels_area,
+            today_area,
+            nowcast_area,
+        ] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(DIGIT_HEIGHT),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::Center)
+        .areas(area);
+
+        Paragraph::new(self.location.as_str())
+            .bold()
+            .centered()
+            .render(location_area, buf);
+
+        let temp = current.temperature_2m.round();
+        let degree = self.icons.degree();
+        let digits = big_text(&format!("{:.0}{}F", temp, degree), self.icons.block());
+        let digits_width = digits.first().map_or(0, |line| line.chars().count()) as u16;
+        let [art_area, digits_area] = Layout::horizontal([
+            Constraint::Length(ART_WIDTH),
+            Constraint::Length(digits_width),
+        ])
+        .spacing(4)
+        .flex(Flex::Center)
+        .areas(big_area);
+
+        let (art, color) = art(current.weather_code, &self.theme);
+        Paragraph::new(Text::from_iter(art))
+            .style(color.map_or(Style::new(), |color| Style::new().fg(color)))
+            .render(art_area, buf);
+        let temp_style = self
+            .gradient
+            .color(temp)
+            .map_or(Style::new(), |color| Style::new().fg(color));
+        Paragraph::new(Text::from_iter(digits))
+            .style(temp_style)
+            .render(digits_area, buf);
+
+        Paragraph::new(get_weather_description(current.weather_code))
+            .bold()
+            .centered()
+            .render(desc_area, buf);
+        Paragraph::new(format!(
+            "Feels like {:.0}{}F · {}% humidity",
+            current.apparent_temperature, degree, current.relative_humidity_2m
+        ))
+        .centered()
+        .render(feels_area, buf);
+        if let Some((high, low)) = self.today {
+            Paragraph::new(format!(
+                "High {:.0}{}F · Low {:.0}{}F",
+                high, degree, low, degree
+            ))
+            .centered()
+            .render(today_area, buf);
+        }
+        if let Some(nowcast) = self.nowcast {
+            Paragraph::new(Line::styled(
+                nowcast.summary(),
+                Style::new().fg(self.theme.precip),
+            ))
+            .centered()
+            .render(nowcast_area, buf);
+        }
+    }
+}
+
+impl BigConditions {
+    pub fn new(location: String, current: Option<OpenMeteoCurrent>) -> Self {
+        Self {
+            location,
+            data: current,
+            today: None,
+            nowcast: None,
+            theme: Theme::default(),
+            icons: IconSet::default(),
+            gradient: TemperatureGradient::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    pub fn gradient(mut self, gradient: TemperatureGradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn today(mut self, today: Option<(f32, f32)>) -> Self {
+        self.today = today;
+        self
+    }
+
+    pub fn nowcast(mut self, nowcast: Option<Nowcast>) -> Self {
+        self.nowcast = nowcast;
+        self
+    }
+}
+
+/// Five lines of art for the same WMO code groups as
+/// `get_weather_description`, with the color to draw them in
+fn art(code: u16, theme: &Theme) -> ([&'static str; 5], Option<Color>) {
+    const CLOUD: [&str; 3] = ["     .-.     ", "    (   ).   ", "   (___(__)  "];
+    let with_cloud = |below: [&'static str; 2]| [CLOUD[0], CLOUD[1], CLOUD[2], below[0], below[1]];
+
+    match code {
+        0 | 1 => (
+            [
+                "    \\   /    ",
+                "     .-.     ",
+                "  - (   ) -  ",
+                "     `-'     ",
+                "    /   \\    ",
+            ],
+            Some(theme.caution),
+        ),
+        2 => (
+            [
+                "   \\  /      ",
+                " _ /\"\".-.    ",
+                "   \\_(   ).  ",
+                "   /(___(__) ",
+                "             ",
+            ],
+            None,
+        ),
+        3 => (
+            [
+                "             ",
+                "     .--.    ",
+                "  .-(    ).  ",
+                " (___.__)__) ",
+                "             ",
+            ],
+            Some(theme.muted),
+        ),
+        45 | 48 => (
+            [
+                "             ",
+                " _ - _ - _ - ",
+                "  _ - _ - _  ",
+                " _ - _ - _ - ",
+                "             ",
+            ],
+            Some(theme.muted),
+        ),
+        51..=55 | 61 | 80 => (
+            with_cloud(["    ' ' ' '  ", "   ' ' ' '   "]),
+            Some(theme.precip),
+        ),
+        63 | 65 | 81 | 82 => (
+            with_cloud(["  ,',',',',  ", "  ,',',',',  "]),
+            Some(theme.precip),
+        ),
+        56 | 57 | 66 | 67 => (
+            with_cloud(["    ' * ' *  ", "   * ' * '   "]),
+            Some(theme.precip),
+        ),
+        71..=77 | 85 | 86 => (with_cloud(["    *  *  *  ", "   *  *  *   "]), None),
+        95..=99 => (
+            with_cloud(["    ,/_ ,/_  ", "     /   /   "]),
+            Some(theme.caution),
+        ),
+        _ => (
+            [
+                "     .-.     ",
+                "      __)    ",
+                "     (       ",
+                "      `-'    ",
+                "       .     ",
+            ],
+            None,
+        ),
+    }
+}
+
+/// `text` in a five line block font drawn with `fill`, only digits, `-`,
+/// `.`, `°` and `F`
+fn big_text(text: &str, fill: char) -> Vec<String> {
+    let glyphs: Vec<[&str; 5]> = text
+        .chars()
+        .map(|c| match c {
+            '0' => ["███", "█ █", "█ █", "█ █", "███"],
+            '1' => ["██ ", " █ ", " █ ", " █ ", "███"],
+            '2' => ["███", "  █", "███", "█  ", "███"],
+            '3' => ["███", "  █", "███", "  █", "███"],
+            '4' => ["█ █", "█ █", "███", "  █", "  █"],
+            '5' => ["███", "█  ", "███", "  █", "███"],
+            '6' => ["███", "█  ", "███", "█ █", "███"],
+            '7' => ["███", "  █", "  █", "  █", "  █"],
+            '8' => ["███", "█ █", "███", "█ █", "███"],
+            '9' => ["███", "█ █", "███", "  █", "███"],
+            '-' => ["   ", "   ", "███", "   ", "   "],
+            '.' => [" ", " ", " ", " ", "█"],
+            '°' => [" █ ", "█ █", " █ ", "   ", "   "],
+            'F' => ["███", "█  ", "██ ", "█  ", "█  "],
+            _ => ["   ", "   ", "   ", "   ", "   "],
+        })
+        .collect();
+
+    (0..DIGIT_HEIGHT as usize)
+        .map(|row| {
+            glyphs
+                .iter()
+                .map(|glyph| glyph[row].replace('█', &fill.to_string()))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn big_text_uses_the_fill() {
+        let lines = big_text("-7°F", IconSet::Ascii.block());
+        assert_eq!(lines.len(), DIGIT_HEIGHT as usize);
+        assert!(lines.iter().all(|line| line.is_ascii()));
+        assert_eq!(lines[0], "    ###  #  ###");
+    }
+}
@@ -0,0 +1,94 @@
+use ratatui::{
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Block, Clear, List, ListItem, ListState, StatefulWidget, Widget},
+};
+
+use crate::keymap::Action;
+use crate::theme::Theme;
+use crate::widgets::weather_table::Column;
+
+/// Popup for toggling hourly table columns. Enabled columns keep the order
+/// they were configured in and newly enabled ones are appended.
+#[derive(Debug, Default, Clone)]
+pub struct ColumnPicker {
+    enabled: Vec<Column>,
+    selected: usize,
+    open: bool,
+    theme: Theme,
+    hint: String,
+}
+
+impl Widget for ColumnPicker {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let items = Column::ALL.iter().map(|column| {
+            let check = if self.enabled.contains(column) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            ListItem::new(Line::from(format!("{} {}", check, column.label())))
+        });
+
+        let list = List::new(items)
+            .block(
+                Block::bordered()
+                    .title("Columns")
+                    .title_bottom(Line::from(self.hint).centered()),
+            )
+            .highlight_style(Style::new().fg(self.theme.accent).bold());
+
+        Clear.render(area, buf);
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        StatefulWidget::render(list, area, buf, &mut state);
+    }
+}
+
+impl ColumnPicker {
+    pub fn new(columns: Vec<Column>) -> Self {
+        Self {
+            enabled: columns,
+            ..Default::default()
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The popup's own keys, shown on its bottom border
+    pub fn hint(mut self, hint: String) -> Self {
+        self.hint = hint;
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle_open(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.enabled
+    }
+
+    pub fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Columns | Action::ClosePopup | Action::Confirm => self.open = false,
+            Action::NextHour => self.selected = (self.selected + 1).min(Column::ALL.len() - 1),
+            Action::PreviousHour => self.selected = self.selected.saturating_sub(1),
+            Action::ToggleColumn => {
+                let column = Column::ALL[self.selected];
+                if let Some(index) = self.enabled.iter().position(|c| *c == column) {
+                    self.enabled.remove(index);
+                } else {
+                    self.enabled.push(column);
+                }
+            }
+            _ => {}
+        }
+    }
+}
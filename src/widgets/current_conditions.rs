@@ -0,0 +1,113 @@
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::data::weather::{OpenMeteoCurrent, Weather, get_weather_description};
+use crate::{icons::IconSet, theme::Theme};
+
+/// Differences smaller than this read as "same as yesterday", in °F
+const SAME_THRESHOLD: f32 = 0.5;
+
+#[derive(Default)]
+pub struct CurrentConditions {
+    data: Option<OpenMeteoCurrent>,
+    yesterday: Option<Weather>,
+    high_delta: Option<f32>,
+    theme: Theme,
+    icons: IconSet,
+}
+
+impl Widget for CurrentConditions {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let Some(current) = self.data else {
+            return;
+        };
+        let [now_area, delta_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(area);
+
+        let mut now = vec![
+            Span::raw("Now ").bold(),
+            Span::raw(format!(
+                "{} {} · {:.1}°F · feels {:.1}°F · {}% humidity",
+                self.icons.weather(current.weather_code),
+                get_weather_description(current.weather_code),
+                current.temperature_2m,
+                current.apparent_temperature,
+                current.relative_humidity_2m
+            )),
+        ];
+        if current.precipitation > 0.0 {
+            now.push(Span::raw(format!(" · {:.1} mm", current.precipitation)));
+        }
+        Paragraph::new(Line::from(now))
+            .centered()
+            .render(now_area, buf);
+
+        let mut spans = Vec::new();
+        if let Some(yesterday) = self.yesterday.and_then(|yesterday| yesterday.temp) {
+            spans.push(Self::delta(
+                current.temperature_2m - yesterday,
+                "than this time yesterday",
+                &self.theme,
+            ));
+        }
+        if let Some(high_delta) = self.high_delta {
+            if !spans.is_empty() {
+                spans.push(Span::raw(" · "));
+            }
+            spans.push(Self::delta(high_delta, "high than yesterday", &self.theme));
+        }
+        Paragraph::new(Line::from(spans))
+            .centered()
+            .render(delta_area, buf);
+    }
+}
+
+impl CurrentConditions {
+    /// `yesterday` is the hour 24 hours before now and `high_delta` today's
+    /// forecast high minus yesterday's, both only known with past days
+    /// requested.
+    pub fn new(
+        current: Option<OpenMeteoCurrent>,
+        yesterday: Option<Weather>,
+        high_delta: Option<f32>,
+    ) -> Self {
+        Self {
+            data: current,
+            yesterday,
+            high_delta,
+            theme: Theme::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    fn delta(change: f32, comparison: &str, theme: &Theme) -> Span<'static> {
+        if change.abs() < SAME_THRESHOLD {
+            let comparison = comparison.replacen("than", "as", 1);
+            Span::styled(format!("Same {}", comparison), Style::new().fg(theme.muted))
+        } else if change > 0.0 {
+            Span::styled(
+                format!("{:.0}° warmer {}", change, comparison),
+                Style::new().fg(theme.warm),
+            )
+        } else {
+            Span::styled(
+                format!("{:.0}° cooler {}", -change, comparison),
+                Style::new().fg(theme.cold),
+            )
+        }
+    }
+}
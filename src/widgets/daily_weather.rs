@@ -1,36 +1,68 @@
-use chrono::{Datelike, NaiveDate};
+use std::collections::HashMap;
+
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime};
 use ratatui::{
     layout::{Constraint, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use crate::data::weather::{OpenMeteoDaily, get_weather_description};
+use crate::data::{
+    alert_rules::AlertSpan,
+    climate::ClimateContext,
+    weather::{OpenMeteoDaily, at, get_weather_description},
+};
+use crate::{gradient::TemperatureGradient, icons::IconSet, theme::Theme};
+
+/// How the days are laid out, the app picks one to fit the terminal
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DayView {
+    /// A card per day side by side
+    #[default]
+    Cards,
+    /// A line per day
+    List,
+    /// Only the selected day on one line
+    Single,
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct DailyWeather {
     data: OpenMeteoDaily,
     selected_date: String,
+    alerts: Vec<AlertSpan>,
+    climate: HashMap<String, ClimateContext>,
+    /// Ensemble uncertainty of each day's high, keyed by date string
+    spread: HashMap<String, f32>,
+    theme: Theme,
+    gradient: TemperatureGradient,
+    icons: IconSet,
+    view: DayView,
+    /// Most days shown at once, scrolling to keep the selected day in view
+    visible: Option<usize>,
 }
 
 impl Widget for DailyWeather {
     fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
-        let horizontal =
-            Layout::horizontal((0..self.data.date.len()).map(|_| Constraint::Fill(2))).spacing(2);
-
-        let rows = Layout::vertical([Constraint::Length(6)])
-            .spacing(1)
-            .split(area);
-
-        let cells = rows.iter().flat_map(|&row| horizontal.split(row).to_vec());
+        let now = Local::now().naive_local();
+        match self.view {
+            DayView::Cards => self.render_cards(area, buf, now),
+            DayView::List => self.render_list(area, buf, now),
+            DayView::Single => self.render_single(area, buf, now),
+        }
+    }
+}
 
-        for (i, cell) in cells.enumerate() {
+impl DailyWeather {
+    fn render_cards(&self, area: Rect, buf: &mut ratatui::prelude::Buffer, now: NaiveDateTime) {
+        for (i, cell) in self.card_areas(area) {
             fn calc_cell(rect: Rect) -> Rect {
                 Rect {
                     x: rect.x + 1,
                     y: rect.y + 1,
-                    width: rect.width - 2,
-                    height: rect.height - 2,
+                    width: rect.width.saturating_sub(2),
+                    height: rect.height.saturating_sub(2),
                 }
             }
             let cell_layout = Layout::vertical([
@@ -38,62 +70,290 @@ impl Widget for DailyWeather {
                 Constraint::Length(1),
                 Constraint::Length(1),
                 Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
             ])
             .split(calc_cell(cell));
 
             let date_str =
                 format_date_with_suffix(self.data.date[i].as_str()).unwrap_or("".to_string());
-            let block = if self.selected_date == self.data.date[i]
-                || (self.selected_date == "" && self.data.date[0] == self.data.date[i])
-            {
-                Block::default().style(Style::new().fg(Color::LightBlue))
-            } else {
-                Block::default()
-            };
+            // The header takes the color of the day's high
+            let high_style = self.high_style(i);
+            let mut title = vec![Span::styled(date_str, high_style)];
+            title.extend(self.alert_badge(i, now));
+            let block = Block::default().style(self.day_style(i));
 
             block
                 .borders(Borders::all())
-                .title(date_str)
+                .title(Line::from(title))
                 .render(cell, buf);
-            let (weather_desc, weather_emoji) = get_weather_description(self.data.weather_code[i]);
+            Paragraph::new(self.condition(i)).render(cell_layout[0], buf);
 
-            Paragraph::new(format!("{} {}", weather_emoji, weather_desc))
-                .render(cell_layout[0], buf);
+            let mut temp = format!(
+                "{} {}",
+                self.icons.temperature(),
+                or_missing(at(&self.data.temperature_2m_max, i), |high| {
+                    format!("{:.1}°F", high)
+                })
+            );
+            if let Some(spread) = self.spread.get(&self.data.date[i]) {
+                temp.push_str(&format!(" ±{:.0}°", spread));
+            }
+            Paragraph::new(Line::styled(temp, high_style)).render(cell_layout[1], buf);
 
-            Paragraph::new(format!("🌡️ {:.1}°F", self.data.temperature_2m_max[i]))
-                .render(cell_layout[1], buf);
+            let feels = match at(&self.data.apparent_temperature_max, i) {
+                Some(feels) => format!("{} {:.1}°F", self.icons.feels_like(feels), feels),
+                None => format!("{} –", self.icons.temperature()),
+            };
+            Paragraph::new(feels).render(cell_layout[2], buf);
 
-            let feels_temp = self.data.apparent_temperature_max[i];
-            let feels_emoji = if feels_temp < 50.0 { "🥶" } else { "🥵" };
+            Paragraph::new(format!("{} {}", self.icons.precipitation(), self.precip(i)))
+                .render(cell_layout[3], buf);
 
-            Paragraph::new(format!(
-                "{} {:.1}°F",
-                feels_emoji, self.data.apparent_temperature_max[i]
-            ))
-            .render(cell_layout[2], buf);
+            if let Some(context) = self.climate.get(&self.data.date[i]) {
+                let (direction, color) = if context.high_delta >= 0.0 {
+                    ("above", self.theme.warm)
+                } else {
+                    ("below", self.theme.cold)
+                };
+                Paragraph::new(Line::styled(
+                    format!("{:+.0}° {} normal", context.high_delta, direction),
+                    Style::new().fg(color),
+                ))
+                .render(cell_layout[4], buf);
 
-            Paragraph::new(format!(
-                "☔️ {}%",
-                self.data.precipitation_probability_max[i]
-            ))
-            .render(cell_layout[3], buf);
+                Paragraph::new(format!(
+                    "Low {:+.0}° · p{}",
+                    context.low_delta, context.percentile
+                ))
+                .render(cell_layout[5], buf);
+            }
         }
     }
-}
 
-impl DailyWeather {
+    fn card_areas(&self, area: Rect) -> Vec<(usize, Rect)> {
+        let days = self.visible_days();
+        let horizontal = Layout::horizontal(days.clone().map(|_| Constraint::Fill(2))).spacing(2);
+
+        let rows = Layout::vertical([Constraint::Length(self.height())])
+            .spacing(1)
+            .split(area);
+
+        let cells = rows.iter().flat_map(|&row| horizontal.split(row).to_vec());
+        days.zip(cells).collect()
+    }
+
+    fn render_list(&self, area: Rect, buf: &mut ratatui::prelude::Buffer, now: NaiveDateTime) {
+        let lines = self.visible_days().map(|i| {
+            let date_str = format_date_with_suffix(&self.data.date[i]).unwrap_or_default();
+            let code = at(&self.data.weather_code, i);
+            let degrees = |temp: Option<f32>| or_missing(temp, |temp| format!("{:.0}°", temp));
+            let mut high = format!("{:>5}", degrees(at(&self.data.temperature_2m_max, i)));
+            if let Some(spread) = self.spread.get(&self.data.date[i]) {
+                high.push_str(&format!(" ±{:.0}", spread));
+            }
+            let mut spans = vec![
+                Span::raw(format!("{:<15}", date_str)),
+                Span::raw(format!(
+                    "{} ",
+                    code.map_or(" ".to_string(), |code| self.icons.weather(code))
+                )),
+                Span::styled(format!("{:<8}", high), self.high_style(i)),
+                Span::raw(format!(
+                    "{:>5}  {} {:>4}  {}",
+                    degrees(at(&self.data.temperature_2m_min, i)),
+                    self.icons.precipitation(),
+                    self.precip(i),
+                    code.map_or("", get_weather_description)
+                )),
+            ];
+            spans.extend(self.alert_badge(i, now));
+            Line::from(spans).style(self.day_style(i))
+        });
+
+        Paragraph::new(lines.collect::<Vec<_>>())
+            .block(Block::bordered().title("Daily"))
+            .render(area, buf);
+    }
+
+    fn render_single(&self, area: Rect, buf: &mut ratatui::prelude::Buffer, now: NaiveDateTime) {
+        let Some(i) = self.selected_index() else {
+            return;
+        };
+        let degrees = |temp: Option<f32>| or_missing(temp, |temp| format!("{:.0}°F", temp));
+        let mut spans = vec![
+            Span::styled(
+                format!(
+                    "< {} > ",
+                    format_date_with_suffix(&self.data.date[i]).unwrap_or_default()
+                ),
+                Style::new().fg(self.theme.accent),
+            ),
+            Span::raw(format!("{} · ", self.condition(i))),
+            Span::styled(
+                degrees(at(&self.data.temperature_2m_max, i)),
+                self.high_style(i),
+            ),
+            Span::raw(format!(
+                " / {} · {} {}",
+                degrees(at(&self.data.temperature_2m_min, i)),
+                self.icons.precipitation(),
+                self.precip(i)
+            )),
+        ];
+        spans.extend(self.alert_badge(i, now));
+        Paragraph::new(Line::from(spans))
+            .centered()
+            .render(area, buf);
+    }
+
+    /// Where each day is drawn in `area`, to find the day under the mouse
+    pub fn day_areas(&self, area: Rect) -> Vec<(usize, Rect)> {
+        match self.view {
+            DayView::Cards => self.card_areas(area),
+            DayView::List => {
+                // Inside the border, one line per day
+                let inner = Block::bordered().inner(area);
+                self.visible_days().zip(inner.rows()).collect()
+            }
+            DayView::Single => vec![],
+        }
+    }
+
+    fn visible_days(&self) -> std::ops::Range<usize> {
+        let total = self.data.date.len();
+        let count = self.visible.unwrap_or(total).min(total);
+        let selected = self.selected_index().unwrap_or(0);
+        let start = (selected + 1).saturating_sub(count);
+        start..start + count
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.data
+            .date
+            .iter()
+            .position(|date| *date == self.selected_date)
+            .or((!self.data.date.is_empty()).then_some(0))
+    }
+
+    /// Selected days are highlighted and past days dimmed
+    fn day_style(&self, i: usize) -> Style {
+        let is_past = NaiveDate::parse_from_str(self.data.date[i].as_str(), "%Y-%m-%d")
+            .is_ok_and(|date| date < Local::now().date_naive());
+        if self.selected_index() == Some(i) {
+            Style::new().fg(self.theme.accent)
+        } else if is_past {
+            Style::new().fg(self.theme.muted)
+        } else {
+            Style::new()
+        }
+    }
+
+    fn high_style(&self, i: usize) -> Style {
+        at(&self.data.temperature_2m_max, i)
+            .and_then(|high| self.gradient.color(high))
+            .map_or(Style::new(), |color| Style::new().fg(color))
+    }
+
+    fn condition(&self, i: usize) -> String {
+        or_missing(at(&self.data.weather_code, i), |code| {
+            format!(
+                "{} {}",
+                self.icons.weather(code),
+                get_weather_description(code)
+            )
+        })
+    }
+
+    fn precip(&self, i: usize) -> String {
+        or_missing(at(&self.data.precipitation_probability_max, i), |precip| {
+            format!("{}%", precip)
+        })
+    }
+
+    /// Alerts still to come on the day, hours already past don't count
+    fn alert_badge(&self, i: usize, now: NaiveDateTime) -> Option<Span<'static>> {
+        let alert_count =
+            NaiveDate::parse_from_str(self.data.date[i].as_str(), "%Y-%m-%d").map_or(0, |date| {
+                self.alerts
+                    .iter()
+                    .filter(|alert| alert.touches(date) && !alert.is_over(now))
+                    .count()
+            });
+        (alert_count > 0).then(|| {
+            Span::styled(
+                format!(" {} {}", self.icons.warning(), alert_count),
+                Style::new().fg(self.theme.alert),
+            )
+        })
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn gradient(mut self, gradient: TemperatureGradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    /// Keeps the selected day across refreshes, otherwise starts on today so
+    /// past days shown before it aren't selected by default
     pub fn data(&mut self, data: OpenMeteoDaily) {
-        if self.data.date.len() == 0 {
-            self.selected_date = self
-                .data
-                .date
-                .first()
-                .map_or("".to_string(), |date| date.to_string());
+        if !data.date.contains(&self.selected_date) {
+            let today = Local::now().date_naive().format("%Y-%m-%d").to_string();
+            self.selected_date = if data.date.contains(&today) {
+                today
+            } else {
+                data.date
+                    .first()
+                    .map_or("".to_string(), |date| date.to_string())
+            };
         }
         self.data = data;
     }
 
+    pub fn climate(&mut self, climate: HashMap<String, ClimateContext>) {
+        self.climate = climate;
+    }
+
+    pub fn spread(&mut self, spread: HashMap<String, f32>) {
+        self.spread = spread;
+    }
+
+    pub fn view(&mut self, view: DayView) {
+        self.view = view;
+    }
+
+    pub fn visible(&mut self, days: Option<usize>) {
+        self.visible = days;
+    }
+
+    /// Rows needed for the current view, cards are taller once there are
+    /// climate normals to show
+    pub fn height(&self) -> u16 {
+        match self.view {
+            DayView::Cards if self.climate.is_empty() => 6,
+            DayView::Cards => 8,
+            DayView::List => self.visible_days().len() as u16 + 2,
+            DayView::Single => 1,
+        }
+    }
+
+    pub fn alerts(&mut self, alerts: Vec<AlertSpan>) {
+        self.alerts = alerts;
+    }
+
     pub fn select_next(&mut self) {
+        if self.data.date.is_empty() {
+            return;
+        }
         let next = self
             .data
             .date
@@ -111,6 +371,9 @@ impl DailyWeather {
     }
 
     pub fn select_previous(&mut self) {
+        if self.data.date.is_empty() {
+            return;
+        }
         let prev = self
             .data
             .date
@@ -126,18 +389,29 @@ impl DailyWeather {
         self.selected_date = self.data.date[index].clone();
     }
 
+    pub fn select(&mut self, index: usize) {
+        if let Some(date) = self.data.date.get(index) {
+            self.selected_date = date.clone();
+        }
+    }
+
     pub fn selected(self) -> NaiveDate {
         NaiveDate::parse_from_str(self.selected_date.as_str(), "%Y-%m-%d").unwrap_or_default()
     }
 }
 
+/// Values the model didn't provide show as a dash
+fn or_missing<T>(value: Option<T>, format: impl FnOnce(T) -> String) -> String {
+    value.map_or("–".to_string(), format)
+}
+
 fn format_date_with_suffix(input: &str) -> Option<String> {
     let date = NaiveDate::parse_from_str(input, "%Y-%m-%d").ok()?;
     let weekday = date.format("%a").to_string(); // "Sun"
     let month = date.format("%b").to_string(); // "Jun"
     let day = date.day();
     let suffix = match day {
-        11 | 12 | 13 => "th",
+        11..=13 => "th",
         _ => match day % 10 {
             1 => "st",
             2 => "nd",
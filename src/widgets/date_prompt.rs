@@ -0,0 +1,202 @@
+use chrono::{Local, NaiveDate, TimeDelta};
+use ratatui::{
+    crossterm::event::KeyEvent,
+    prelude::{Buffer, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Clear, Widget},
+};
+use tui_textarea::TextArea;
+
+use crate::{keymap::Action, theme::Theme};
+
+/// The day strip only has room for a week of cards
+const MAX_HISTORY_DAYS: i64 = 7;
+/// The archive is built from reanalysis that lags about five days behind,
+/// more recent days come back empty
+const ARCHIVE_LAG_DAYS: i64 = 5;
+
+/// Popup asking for a past date or `start..end` range to look up
+#[derive(Clone)]
+pub struct DatePrompt {
+    textarea: TextArea<'static>,
+    error: Option<String>,
+    open: bool,
+    theme: Theme,
+}
+
+impl Default for DatePrompt {
+    fn default() -> Self {
+        let mut textarea = TextArea::default();
+        textarea.set_cursor_line_style(Style::default());
+        textarea.set_placeholder_text("2024-07-04 or 2024-07-01..2024-07-07");
+        Self {
+            textarea,
+            error: None,
+            open: false,
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl Widget for DatePrompt {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
+        let mut block = Block::bordered().title("History");
+        if let Some(error) = self.error {
+            block = block.title_bottom(Line::styled(error, Style::new().fg(self.theme.error)));
+        }
+        self.textarea.set_block(block);
+        Clear.render(area, buf);
+        self.textarea.render(area, buf);
+    }
+}
+
+impl DatePrompt {
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.error = None;
+    }
+
+    /// Typing and editing keys for the date box
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) {
+        self.textarea.input(key_event);
+    }
+
+    /// Returns the requested range once the user submits a valid one
+    pub fn handle_action(&mut self, action: Action) -> Option<(NaiveDate, NaiveDate)> {
+        match action {
+            Action::History | Action::ClosePopup => self.open = false,
+            Action::Confirm => match Self::parse_range(&self.textarea.lines()[0]) {
+                Ok(range) => {
+                    self.open = false;
+                    return Some(range);
+                }
+                Err(error) => self.error = Some(error),
+            },
+            _ => {}
+        }
+        None
+    }
+
+    fn parse_range(input: &str) -> Result<(NaiveDate, NaiveDate), String> {
+        let parse = |date: &str| {
+            NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| format!("\"{}\" isn't a YYYY-MM-DD date", date.trim()))
+        };
+        let (start, end) = match input.split_once("..") {
+            Some((start, end)) => (parse(start)?, parse(end)?),
+            None => {
+                let date = parse(input)?;
+                (date, date)
+            }
+        };
+
+        if start > end {
+            return Err("Start date is after end date".to_string());
+        }
+        let today = Local::now().date_naive();
+        if end >= today {
+            return Err("Dates must be in the past".to_string());
+        }
+        let latest = today - TimeDelta::days(ARCHIVE_LAG_DAYS);
+        if end > latest {
+            return Err(format!(
+                "No data yet, the archive goes up to {}",
+                latest.format("%Y-%m-%d")
+            ));
+        }
+        if end - start >= TimeDelta::days(MAX_HISTORY_DAYS) {
+            return Err(format!("Pick at most {} days", MAX_HISTORY_DAYS));
+        }
+
+        Ok((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days_ago(days: i64) -> NaiveDate {
+        Local::now().date_naive() - TimeDelta::days(days)
+    }
+
+    fn range(start: NaiveDate, end: NaiveDate) -> String {
+        format!("{}..{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
+    }
+
+    #[test]
+    fn single_dates_and_ranges_parse() {
+        assert_eq!(
+            DatePrompt::parse_range("2024-07-04"),
+            Ok((
+                NaiveDate::from_ymd_opt(2024, 7, 4).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 7, 4).unwrap()
+            ))
+        );
+        let (start, end) = (days_ago(30), days_ago(30 - MAX_HISTORY_DAYS + 1));
+        assert_eq!(
+            DatePrompt::parse_range(&range(start, end)),
+            Ok((start, end))
+        );
+    }
+
+    #[test]
+    fn reversed_ranges_are_rejected() {
+        let input = range(days_ago(20), days_ago(25));
+        assert_eq!(
+            DatePrompt::parse_range(&input),
+            Err("Start date is after end date".to_string())
+        );
+    }
+
+    #[test]
+    fn ranges_longer_than_the_day_strip_are_rejected() {
+        let input = range(days_ago(30), days_ago(30 - MAX_HISTORY_DAYS));
+        assert_eq!(
+            DatePrompt::parse_range(&input),
+            Err(format!("Pick at most {} days", MAX_HISTORY_DAYS))
+        );
+    }
+
+    #[test]
+    fn dates_the_archive_lacks_are_rejected() {
+        let latest = days_ago(ARCHIVE_LAG_DAYS).format("%Y-%m-%d").to_string();
+        assert_eq!(
+            DatePrompt::parse_range(
+                &days_ago(ARCHIVE_LAG_DAYS - 1)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            ),
+            Err(format!("No data yet, the archive goes up to {}", latest))
+        );
+        assert!(DatePrompt::parse_range(&latest).is_ok());
+        assert_eq!(
+            DatePrompt::parse_range(&days_ago(0).format("%Y-%m-%d").to_string()),
+            Err("Dates must be in the past".to_string())
+        );
+    }
+
+    #[test]
+    fn malformed_input_names_the_bad_part() {
+        assert_eq!(
+            DatePrompt::parse_range("July 4th"),
+            Err("\"July 4th\" isn't a YYYY-MM-DD date".to_string())
+        );
+        assert_eq!(
+            DatePrompt::parse_range("2024-07-01..2024-07-32"),
+            Err("\"2024-07-32\" isn't a YYYY-MM-DD date".to_string())
+        );
+        assert!(DatePrompt::parse_range("").is_err());
+        assert!(DatePrompt::parse_range("2024-07-01..").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Style,
+    widgets::{Block, Widget},
+};
+
+use crate::data::ensemble::EnsembleSpread;
+use crate::{icons::IconSet, theme::Theme};
+
+/// Cells reserved left of the plot for temperature labels
+const AXIS_WIDTH: u16 = 5;
+
+/// Hourly temperature of the ensemble median, with the range between the
+/// coldest and warmest member shaded behind it
+#[derive(Default)]
+pub struct EnsembleChart {
+    data: EnsembleSpread,
+    theme: Theme,
+    icons: IconSet,
+}
+
+impl Widget for EnsembleChart {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let block = Block::bordered().title("Ensemble spread · min / median / max");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [axis_area, plot_area] =
+            Layout::horizontal([Constraint::Length(AXIS_WIDTH), Constraint::Fill(1)]).areas(inner);
+        let [plot_area, days_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(plot_area);
+        let hours = self.data.date_time.len();
+        if hours == 0 || plot_area.width == 0 || plot_area.height < 2 {
+            return;
+        }
+
+        let low = self
+            .data
+            .min
+            .iter()
+            .copied()
+            .fold(f32::MAX, f32::min)
+            .floor();
+        let high = self
+            .data
+            .max
+            .iter()
+            .copied()
+            .fold(f32::MIN, f32::max)
+            .ceil();
+        let range = (high - low).max(1.0);
+        let rows = plot_area.height - 1;
+        let row_of =
+            |value: f32| plot_area.y + ((high - value) / range * rows as f32).round() as u16;
+
+        for value in [high, (high + low) / 2.0, low] {
+            buf.set_string(
+                axis_area.x,
+                row_of(value),
+                format!("{:>3.0}°", value),
+                Style::new().fg(self.theme.muted),
+            );
+        }
+
+        let width = plot_area.width as usize;
+        let mut previous_day = None;
+        for column in 0..width {
+            // Several hours share a column on narrow terminals
+            let start = column * hours / width;
+            let end = ((column + 1) * hours / width).max(start + 1).min(hours);
+            let min = self.data.min[start..end]
+                .iter()
+                .copied()
+                .fold(f32::MAX, f32::min);
+            let max = self.data.max[start..end]
+                .iter()
+                .copied()
+                .fold(f32::MIN, f32::max);
+            let median = self.data.median[start..end].iter().sum::<f32>() / (end - start) as f32;
+            let x = plot_area.x + column as u16;
+
+            for y in row_of(max)..=row_of(min) {
+                buf[(x, y)].set_bg(self.theme.band);
+            }
+            buf[(x, row_of(median))]
+                .set_symbol(self.icons.line())
+                .set_fg(self.theme.caution);
+
+            let day = self.data.date_time[start].date();
+            if previous_day != Some(day) && x + 3 <= plot_area.right() {
+                buf.set_string(x, days_area.y, day.format("%a").to_string(), Style::new());
+            }
+            previous_day = Some(day);
+        }
+    }
+}
+
+impl EnsembleChart {
+    pub fn new(spread: EnsembleSpread) -> Self {
+        Self {
+            data: spread,
+            theme: Theme::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+}
@@ -0,0 +1,105 @@
+use ratatui::{
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph, Widget},
+};
+
+use crate::{keymap::Action, theme::Theme};
+
+/// Popup listing every key binding, grouped by where it applies
+#[derive(Debug, Default, Clone)]
+pub struct Help {
+    sections: Vec<(String, Vec<(String, String)>)>,
+    scroll: u16,
+    open: bool,
+    theme: Theme,
+    hint: String,
+}
+
+impl Widget for Help {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let key_width = self
+            .sections
+            .iter()
+            .flat_map(|(_, bindings)| bindings.iter().map(|(keys, _)| keys.chars().count()))
+            .max()
+            .unwrap_or(0);
+
+        let mut lines = vec![];
+        for (title, bindings) in &self.sections {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::styled(
+                title.clone(),
+                Style::new().fg(self.theme.accent).bold(),
+            ));
+            lines.extend(bindings.iter().map(|(keys, action)| {
+                Line::from(vec![
+                    Span::styled(format!("  {:<key_width$}  ", keys), Style::new().bold()),
+                    Span::raw(action.clone()),
+                ])
+            }));
+        }
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .scroll((self.scroll, 0))
+            .block(
+                Block::bordered()
+                    .title("Help")
+                    .title_bottom(Line::from(self.hint).centered()),
+            )
+            .render(area, buf);
+    }
+}
+
+impl Help {
+    pub fn new(sections: Vec<(String, Vec<(String, String)>)>) -> Self {
+        Self {
+            sections,
+            ..Default::default()
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The popup's own keys, shown on its bottom border
+    pub fn hint(mut self, hint: String) -> Self {
+        self.hint = hint;
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn toggle_open(&mut self) {
+        self.open = !self.open;
+        self.scroll = 0;
+    }
+
+    /// Rows needed to show every binding without scrolling
+    pub fn height(&self) -> u16 {
+        let lines: usize = self
+            .sections
+            .iter()
+            .map(|(_, bindings)| bindings.len() + 2)
+            .sum();
+        (lines + 1) as u16
+    }
+
+    pub fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Help | Action::ClosePopup | Action::Confirm => self.toggle_open(),
+            Action::NextHour => {
+                self.scroll = (self.scroll + 1).min(self.height().saturating_sub(3))
+            }
+            Action::PreviousHour => self.scroll = self.scroll.saturating_sub(1),
+            _ => {}
+        }
+    }
+}
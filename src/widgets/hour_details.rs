@@ -0,0 +1,155 @@
+use ratatui::{
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Widget},
+};
+
+use crate::data::weather::{Weather, get_cardinal_direction};
+use crate::{icons::IconSet, theme::Theme};
+
+/// Surface pressure change over three hours that counts as a trend, in hPa
+const PRESSURE_TREND_THRESHOLD: f32 = 1.0;
+
+#[derive(Default)]
+pub struct HourDetails {
+    data: Option<Weather>,
+    earlier: Option<Weather>,
+    theme: Theme,
+    icons: IconSet,
+}
+
+impl Widget for HourDetails {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let Some(weather) = self.data else {
+            return;
+        };
+        let Some(atmosphere) = weather.atmosphere.as_ref() else {
+            return;
+        };
+
+        let title = format!("Details · {}", weather.date_time.format("%-I %p"));
+        let theme = &self.theme;
+        let trend = self
+            .earlier
+            .as_ref()
+            .and_then(|earlier| earlier.atmosphere.as_ref()?.pressure)
+            .zip(atmosphere.pressure)
+            .map(|(earlier, pressure)| Self::pressure_trend(pressure - earlier, theme, self.icons));
+
+        let mut pressure = Self::value(
+            atmosphere
+                .pressure
+                .map(|pressure| format!("{:.0} hPa", pressure)),
+            theme,
+        );
+        if let Some(trend) = trend {
+            pressure.push(Span::raw(" "));
+            pressure.push(trend);
+        }
+        let percent =
+            |value: Option<u16>| Self::value(value.map(|value| format!("{}%", value)), theme);
+
+        let lines = vec![
+            Self::line(
+                "Feels like",
+                Self::value(
+                    weather.apparent_temp.map(|temp| format!("{:.1}°F", temp)),
+                    theme,
+                ),
+            ),
+            Self::line("Humidity", percent(atmosphere.humidity)),
+            Self::line(
+                "Dew point",
+                Self::value(
+                    atmosphere.dew_point.map(|temp| format!("{:.1}°F", temp)),
+                    theme,
+                ),
+            ),
+            Self::line("Pressure", pressure),
+            Self::line(
+                "Wind",
+                Self::value(
+                    atmosphere
+                        .wind_speed
+                        .map(|speed| match atmosphere.wind_direction {
+                            Some(direction) => {
+                                format!("{:.0} mph {}", speed, get_cardinal_direction(direction))
+                            }
+                            None => format!("{:.0} mph", speed),
+                        }),
+                    theme,
+                ),
+            ),
+            Self::line("Cloud cover", percent(atmosphere.cloud_cover)),
+            Self::line("  Low", percent(atmosphere.cloud_cover_low)),
+            Self::line("  Mid", percent(atmosphere.cloud_cover_mid)),
+            Self::line("  High", percent(atmosphere.cloud_cover_high)),
+            Self::line(
+                "Visibility",
+                Self::value(
+                    atmosphere
+                        .visibility
+                        .map(|visibility| format!("{:.1} mi", visibility / 1609.344)),
+                    theme,
+                ),
+            ),
+        ];
+
+        Paragraph::new(lines)
+            .block(Block::bordered().title(title))
+            .render(area, buf);
+    }
+}
+
+impl HourDetails {
+    /// `earlier` is the same location three hours before `weather`, used to
+    /// derive the pressure trend.
+    pub fn new(weather: Option<Weather>, earlier: Option<Weather>) -> Self {
+        Self {
+            data: weather,
+            earlier,
+            theme: Theme::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    fn line(label: &'static str, value: Vec<Span<'static>>) -> Line<'static> {
+        let mut spans = vec![Span::raw(format!("{:<12}", label)).bold()];
+        spans.extend(value);
+        Line::from(spans)
+    }
+
+    /// Values the model didn't provide show as a muted dash
+    fn value(text: Option<String>, theme: &Theme) -> Vec<Span<'static>> {
+        vec![text.map_or(Span::styled("–", Style::new().fg(theme.muted)), Span::raw)]
+    }
+
+    fn pressure_trend(change: f32, theme: &Theme, icons: IconSet) -> Span<'static> {
+        if change >= PRESSURE_TREND_THRESHOLD {
+            Span::styled(
+                format!("{} rising", icons.arrow_up()),
+                Style::new().fg(theme.good),
+            )
+        } else if change <= -PRESSURE_TREND_THRESHOLD {
+            Span::styled(
+                format!("{} falling", icons.arrow_down()),
+                Style::new().fg(theme.caution),
+            )
+        } else {
+            Span::styled(
+                format!("{} steady", icons.arrow_right()),
+                Style::new().fg(theme.muted),
+            )
+        }
+    }
+}
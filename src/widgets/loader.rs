@@ -1,30 +1,38 @@
 use ratatui::{
-    style::{Color, Style},
+    style::Style,
     widgets::{StatefulWidget, Widget},
 };
 use throbber_widgets_tui::{Throbber, ThrobberState};
 
+use crate::theme::Theme;
+
 #[derive(Default, Clone)]
 pub struct Loader {
     tick: usize,
     state: ThrobberState,
+    theme: Theme,
 }
 
 impl Widget for Loader {
     fn render(mut self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
         let throbber = Throbber::default()
             .throbber_set(throbber_widgets_tui::BRAILLE_EIGHT_DOUBLE)
-            .style(Style::new().fg(Color::Cyan));
+            .style(Style::new().fg(self.theme.loader));
 
         StatefulWidget::render(throbber, area, buf, &mut self.state);
     }
 }
 
 impl Loader {
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
     pub fn calc_next(&mut self) {
         self.tick += 1;
 
-        if self.tick % 6 == 0 {
+        if self.tick.is_multiple_of(6) {
             self.state.calc_next();
         }
     }
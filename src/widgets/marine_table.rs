@@ -0,0 +1,95 @@
+use ratatui::{
+    layout::Constraint,
+    style::Stylize,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+use crate::data::{marine::Marine, weather::get_cardinal_direction};
+use crate::{icons::IconSet, theme::Theme};
+
+/// Waves, swell and sea temperature for each hour of a day
+#[derive(Default)]
+pub struct MarineTable {
+    data: Vec<Marine>,
+    theme: Theme,
+    icons: IconSet,
+}
+
+impl Widget for MarineTable {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let header = Row::new(["Time", "Waves", "Period", "Swell", "Swell period", "Sea"])
+            .bold()
+            .bottom_margin(1);
+
+        let rows = self.data.iter().map(|marine| {
+            Row::new([
+                Cell::from(marine.date_time.format("%-I %p").to_string()),
+                Self::height_cell(marine.wave_height, marine.wave_direction, &self.theme),
+                Self::cell(
+                    marine.wave_period.map(|period| format!("{:.0} s", period)),
+                    &self.theme,
+                ),
+                Self::height_cell(marine.swell_height, marine.swell_direction, &self.theme),
+                Self::cell(
+                    marine.swell_period.map(|period| format!("{:.0} s", period)),
+                    &self.theme,
+                ),
+                Self::cell(
+                    marine.sea_temp.map(|temp| format!("{:.1}°F", temp)),
+                    &self.theme,
+                ),
+            ])
+        });
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(header)
+        .block(Block::bordered().title(format!("{}Marine", self.icons.marine())))
+        .render(area, buf);
+    }
+}
+
+impl MarineTable {
+    pub fn new(marine: Vec<Marine>) -> Self {
+        Self {
+            data: marine,
+            theme: Theme::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    fn cell(value: Option<String>, theme: &Theme) -> Cell<'static> {
+        value.map_or(Cell::from("–").fg(theme.muted), Cell::from)
+    }
+
+    fn height_cell(height: Option<f32>, direction: Option<f32>, theme: &Theme) -> Cell<'static> {
+        Self::cell(
+            height.map(|height| match direction {
+                Some(direction) => {
+                    format!("{:.1} ft {}", height, get_cardinal_direction(direction))
+                }
+                None => format!("{:.1} ft", height),
+            }),
+            theme,
+        )
+    }
+}
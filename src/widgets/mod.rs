@@ -1,5 +1,21 @@
+pub mod alerts;
+pub mod astronomy;
+pub mod big_conditions;
+pub mod column_picker;
+pub mod current_conditions;
 pub mod daily_weather;
+pub mod date_prompt;
+pub mod ensemble_chart;
+pub mod help;
+pub mod hour_details;
 pub mod loader;
+pub mod marine_table;
+pub mod model_table;
+pub mod mountain_table;
+pub mod nowcast;
+pub mod pollen;
 pub mod search;
+pub mod tab_bar;
+pub mod temperature_chart;
+pub mod warning_banner;
 pub mod weather_table;
-
@@ -0,0 +1,94 @@
+use chrono::NaiveDateTime;
+use ratatui::{
+    layout::Constraint,
+    style::Stylize,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+use crate::data::models::ModelVariable;
+use crate::theme::Theme;
+
+/// One variable from several models for each hour of a day, with the spread
+/// between the lowest and highest model as a measure of agreement
+pub struct ModelTable {
+    models: Vec<String>,
+    variable: ModelVariable,
+    hours: Vec<(NaiveDateTime, Vec<Option<f32>>)>,
+    /// Key that cycles to the next variable, for the title hint
+    next_key: Option<String>,
+    theme: Theme,
+}
+
+impl Widget for ModelTable {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let header = Row::new(
+            std::iter::once("Time".to_string())
+                .chain(self.models.iter().cloned())
+                .chain(std::iter::once("Spread".to_string())),
+        )
+        .bold()
+        .bottom_margin(1);
+
+        let rows = self.hours.iter().map(|(date_time, values)| {
+            let known: Vec<f32> = values.iter().flatten().copied().collect();
+            let spread = known
+                .iter()
+                .copied()
+                .reduce(f32::max)
+                .zip(known.iter().copied().reduce(f32::min));
+
+            let mut cells = vec![Cell::from(date_time.format("%-I %p").to_string())];
+            cells.extend(values.iter().map(|value| {
+                value.map_or(Cell::from("–").fg(self.theme.muted), |value| {
+                    Cell::from(self.variable.format(value))
+                })
+            }));
+            cells.push(match spread {
+                Some((max, min)) if known.len() > 1 => {
+                    Cell::from(self.variable.format(max - min)).fg(self.theme.caution)
+                }
+                _ => Cell::from(""),
+            });
+            Row::new(cells)
+        });
+
+        let widths = std::iter::once(Constraint::Length(6))
+            .chain(self.models.iter().map(|_| Constraint::Fill(1)))
+            .chain(std::iter::once(Constraint::Fill(1)));
+
+        let mut title = format!("Models · {}", self.variable.label());
+        if let Some(key) = &self.next_key {
+            title.push_str(&format!(" ({} for next)", key));
+        }
+        Table::new(rows, widths)
+            .header(header)
+            .block(Block::bordered().title(title))
+            .render(area, buf);
+    }
+}
+
+impl ModelTable {
+    pub fn new(
+        models: Vec<String>,
+        variable: ModelVariable,
+        hours: Vec<(NaiveDateTime, Vec<Option<f32>>)>,
+    ) -> Self {
+        Self {
+            models,
+            variable,
+            hours,
+            next_key: None,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn next_key(mut self, key: Option<String>) -> Self {
+        self.next_key = key;
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
@@ -0,0 +1,104 @@
+use ratatui::{
+    layout::Constraint,
+    style::Stylize,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+use crate::data::weather::{Mountain, meters_to_feet};
+use crate::theme::Theme;
+
+const FREEZING: f32 = 32.0;
+
+/// Freezing level and temperatures at the forecast elevation and at three
+/// pressure levels above it, for each hour of a day
+#[derive(Default)]
+pub struct MountainTable {
+    data: Vec<Mountain>,
+    /// Meters, the height the surface temperature applies to
+    elevation: f32,
+    theme: Theme,
+}
+
+impl Widget for MountainTable {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let mut header = vec![
+            "Time".to_string(),
+            "Freezing level".to_string(),
+            format!("{:.0} ft", meters_to_feet(self.elevation)),
+        ];
+        if let Some(first) = self.data.first() {
+            header.extend(
+                first
+                    .levels
+                    .iter()
+                    .map(|level| format!("{} hPa", level.pressure)),
+            );
+        }
+        let header = Row::new(header).bold().bottom_margin(1);
+
+        let rows = self.data.iter().map(|mountain| {
+            let mut cells = vec![
+                Cell::from(mountain.date_time.format("%-I %p").to_string()),
+                mountain
+                    .freezing_level
+                    .map_or(Cell::from("–").fg(self.theme.muted), |level| {
+                        Cell::from(format!("{:.0} ft", meters_to_feet(level)))
+                    }),
+                Self::temp_cell(mountain.surface_temp, None, &self.theme),
+            ];
+            cells.extend(
+                mountain
+                    .levels
+                    .iter()
+                    .map(|level| Self::temp_cell(level.temp, level.height, &self.theme)),
+            );
+            Row::new(cells)
+        });
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+            ],
+        )
+        .header(header)
+        .block(Block::bordered().title("Mountain · temperature by altitude"))
+        .render(area, buf);
+    }
+}
+
+impl MountainTable {
+    pub fn new(mountain: Vec<Mountain>, elevation: f32) -> Self {
+        Self {
+            data: mountain,
+            elevation,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    fn temp_cell(temp: Option<f32>, height: Option<f32>, theme: &Theme) -> Cell<'static> {
+        let Some(temp) = temp else {
+            return Cell::from("–").fg(theme.muted);
+        };
+        let text = match height {
+            Some(height) => format!("{:.0}°F @ {:.0} ft", temp, meters_to_feet(height)),
+            None => format!("{:.0}°F", temp),
+        };
+        let cell = Cell::from(text);
+        if temp <= FREEZING {
+            cell.fg(theme.cold)
+        } else {
+            cell
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use ratatui::{
+    layout::{Constraint, Flex, Layout},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Paragraph, Sparkline, Widget},
+};
+
+use crate::data::nowcast::Nowcast;
+use crate::{icons::IconSet, theme::Theme};
+
+/// Precipitation per step that fills a sparkline bar, in mm. Fixed so a
+/// drizzle doesn't look like a downpour.
+const FULL_BAR: f32 = 2.0;
+
+#[derive(Default)]
+pub struct NowcastStrip {
+    data: Option<Nowcast>,
+    theme: Theme,
+    icons: IconSet,
+}
+
+impl Widget for NowcastStrip {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let Some(nowcast) = self.data else {
+            return;
+        };
+
+        let color = if nowcast.raining || nowcast.change_in.is_some() {
+            self.theme.precip
+        } else {
+            self.theme.muted
+        };
+        let summary = Line::from(vec![
+            Span::raw(format!("{} ", self.icons.precipitation())),
+            Span::styled(nowcast.summary(), Style::new().fg(color)),
+        ]);
+        let bars: Vec<u64> = nowcast
+            .precipitation
+            .iter()
+            .map(|precipitation| (precipitation.min(FULL_BAR) * 100.0) as u64)
+            .collect();
+
+        let [summary_area, sparkline_area] = Layout::horizontal([
+            Constraint::Length(summary.width() as u16),
+            Constraint::Length(bars.len() as u16),
+        ])
+        .spacing(2)
+        .flex(Flex::Center)
+        .areas(area);
+
+        Paragraph::new(summary).render(summary_area, buf);
+        Sparkline::default()
+            .data(&bars)
+            .max((FULL_BAR * 100.0) as u64)
+            .style(Style::new().fg(self.theme.precip))
+            .render(sparkline_area, buf);
+    }
+}
+
+impl NowcastStrip {
+    pub fn new(nowcast: Option<Nowcast>) -> Self {
+        Self {
+            data: nowcast,
+            theme: Theme::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+}
@@ -0,0 +1,74 @@
+use ratatui::{
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::data::weather::{Pollen, PollenLevel};
+use crate::{icons::IconSet, theme::Theme};
+
+#[derive(Default)]
+pub struct PollenStrip {
+    data: Vec<Pollen>,
+    theme: Theme,
+    icons: IconSet,
+}
+
+impl Widget for PollenStrip {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let mut spans = vec![Span::raw("Pollen").bold()];
+        // Pollen is forecast for fewer days than the weather
+        if self.data.is_empty() {
+            spans.push(Span::styled(
+                "  no forecast for this day",
+                Style::new().fg(self.theme.muted),
+            ));
+        }
+        for pollen in self.data.iter() {
+            spans.push(Span::raw("  "));
+            spans.push(Span::raw(format!("{} ", pollen.name)));
+            spans.extend(Self::render_level(pollen.level, &self.theme, self.icons));
+        }
+
+        Paragraph::new(Line::from(spans))
+            .centered()
+            .render(area, buf);
+    }
+}
+
+impl PollenStrip {
+    pub fn new(pollen: Vec<Pollen>) -> Self {
+        Self {
+            data: pollen,
+            theme: Theme::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    fn render_level(level: PollenLevel, theme: &Theme, icons: IconSet) -> Vec<Span<'static>> {
+        let (filled, color) = match level {
+            PollenLevel::None => (0, theme.muted),
+            PollenLevel::Low => (1, theme.severity[0]),
+            PollenLevel::Moderate => (2, theme.severity[1]),
+            PollenLevel::High => (3, theme.severity[2]),
+            PollenLevel::VeryHigh => (4, theme.severity[3]),
+        };
+
+        let (full, empty) = icons.meter();
+        vec![
+            Span::styled(full.repeat(filled), Style::new().fg(color)),
+            Span::styled(empty.repeat(4 - filled), Style::new().fg(theme.muted)),
+            Span::styled(format!(" {}", level.label()), Style::new().fg(color)),
+        ]
+    }
+}
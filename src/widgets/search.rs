@@ -1,7 +1,7 @@
 use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    crossterm::event::{KeyCode, KeyEvent},
     prelude::{Buffer, Rect},
-    style::Style,
+    style::{Modifier, Style},
     widgets::{Block, Widget},
 };
 use tui_textarea::{CursorMove, TextArea};
@@ -32,12 +32,8 @@ impl Widget for Search {
 
 impl Search {
     pub fn handle_key_event(&mut self, key_event: KeyEvent) {
-        _ = match (key_event.code, key_event.modifiers) {
-            (KeyCode::Char(' '), KeyModifiers::CONTROL) | (KeyCode::Char('/'), _) => {
-                self.clear_text();
-                true
-            }
-            (KeyCode::Enter, _) => true,
+        _ = match key_event.code {
+            KeyCode::Enter => true,
             _ => {
                 if self.text().len() <= 100 {
                     self.textarea.input(key_event)
@@ -46,7 +42,15 @@ impl Search {
                 }
             }
         };
-        ()
+    }
+
+    /// Only the focused search box shows a cursor
+    pub fn focused(&mut self, focused: bool) {
+        self.textarea.set_cursor_style(if focused {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        });
     }
 
     pub fn text(&self) -> String {
@@ -0,0 +1,115 @@
+use ratatui::{
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+    widgets::{Tabs, Widget},
+};
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::theme::Theme;
+
+/// The views the app switches between
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    #[default]
+    Overview,
+    Hourly,
+    Charts,
+    Days,
+    History,
+    Details,
+    /// Only for coastal locations
+    Marine,
+}
+
+impl Tab {
+    pub const ALL: [Tab; 7] = [
+        Tab::Overview,
+        Tab::Hourly,
+        Tab::Charts,
+        Tab::Days,
+        Tab::History,
+        Tab::Details,
+        Tab::Marine,
+    ];
+
+    /// The tabs to show, Marine only when there's a marine forecast. It
+    /// comes last so hiding it doesn't renumber the others.
+    pub fn available(marine: bool) -> Vec<Tab> {
+        Tab::ALL
+            .into_iter()
+            .filter(|&tab| marine || tab != Tab::Marine)
+            .collect()
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Tab::Overview => "Overview",
+            Tab::Hourly => "Hourly",
+            Tab::Charts => "Charts",
+            Tab::Days => "14-day",
+            Tab::History => "History",
+            Tab::Details => "Details",
+            Tab::Marine => "Marine",
+        }
+    }
+
+    /// Tabs are numbered from 1 in the order of `ALL`
+    pub fn number(&self) -> usize {
+        Tab::ALL.iter().position(|tab| tab == self).unwrap_or(0) + 1
+    }
+}
+
+/// One line listing the tabs with their number keys
+#[derive(Default)]
+pub struct TabBar {
+    tabs: Vec<Tab>,
+    selected: Tab,
+    theme: Theme,
+}
+
+impl Widget for TabBar {
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer) {
+        let titles = self.tabs.iter().map(|tab| Line::from(label(*tab)));
+
+        Tabs::new(titles)
+            .select(self.tabs.iter().position(|&tab| tab == self.selected))
+            .style(Style::new().fg(self.theme.muted))
+            .highlight_style(Style::new().fg(self.theme.accent).bold())
+            .render(area, buf);
+    }
+}
+
+impl TabBar {
+    pub fn new(tabs: Vec<Tab>, selected: Tab) -> Self {
+        Self {
+            tabs,
+            selected,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Where each tab is drawn in `area`, following `Tabs`' one column of
+    /// padding on each side and one column divider
+    pub fn tab_areas(tabs: &[Tab], area: Rect) -> Vec<(Tab, Rect)> {
+        let mut x = area.x;
+        tabs.iter()
+            .map(|&tab| {
+                let width = label(tab).width() as u16 + 2;
+                let tab_area = Rect::new(x, area.y, width, 1).intersection(area);
+                x = x.saturating_add(width + 1);
+                (tab, tab_area)
+            })
+            .collect()
+    }
+}
+
+fn label(tab: Tab) -> String {
+    format!("{} {}", tab.number(), tab.title())
+}
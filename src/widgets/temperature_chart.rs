@@ -0,0 +1,94 @@
+use chrono::Timelike;
+use ratatui::{
+    style::Style,
+    symbols::Marker,
+    text::Line,
+    widgets::{Axis, Block, Chart, Dataset, GraphType, Widget},
+};
+
+use crate::data::weather::Weather;
+use crate::theme::Theme;
+
+/// Temperature and feels-like temperature over the hours of one day
+#[derive(Default)]
+pub struct TemperatureChart {
+    data: Vec<Weather>,
+    theme: Theme,
+}
+
+impl Widget for TemperatureChart {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        if self.data.is_empty() {
+            return;
+        }
+
+        // Hours the model has no value for are left out of the line
+        let point = |weather: &Weather, value: Option<f32>| {
+            Some((weather.date_time.hour() as f64, value? as f64))
+        };
+        let temps: Vec<(f64, f64)> = self
+            .data
+            .iter()
+            .filter_map(|weather| point(weather, weather.temp))
+            .collect();
+        let feels: Vec<(f64, f64)> = self
+            .data
+            .iter()
+            .filter_map(|weather| point(weather, weather.apparent_temp))
+            .collect();
+        if temps.is_empty() && feels.is_empty() {
+            return;
+        }
+
+        let (low, high) = temps
+            .iter()
+            .chain(feels.iter())
+            .fold((f64::MAX, f64::MIN), |(low, high), (_, value)| {
+                (low.min(*value), high.max(*value))
+            });
+        let (low, high) = (low.floor(), high.ceil().max(low.floor() + 1.0));
+
+        let datasets = vec![
+            Dataset::default()
+                .name("Temperature")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().fg(self.theme.warm))
+                .data(&temps),
+            Dataset::default()
+                .name("Feels like")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().fg(self.theme.muted))
+                .data(&feels),
+        ];
+        let label = |text: String| Line::styled(text, Style::new().fg(self.theme.muted));
+
+        Chart::new(datasets)
+            .block(Block::bordered().title("Temperature"))
+            .x_axis(Axis::default().bounds([0.0, 23.0]).labels([
+                label("12 AM".to_string()),
+                label("12 PM".to_string()),
+                label("11 PM".to_string()),
+            ]))
+            .y_axis(Axis::default().bounds([low, high]).labels([
+                label(format!("{:.0}°", low)),
+                label(format!("{:.0}°", high)),
+            ]))
+            .render(area, buf);
+    }
+}
+
+impl TemperatureChart {
+    pub fn new(hourly: Vec<Weather>) -> Self {
+        Self {
+            data: hourly,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+}
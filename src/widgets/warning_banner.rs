@@ -0,0 +1,86 @@
+use ratatui::{
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::data::cap::CapAlert;
+use crate::{icons::IconSet, theme::Theme};
+
+#[derive(Default)]
+pub struct WarningBanner {
+    data: Vec<CapAlert>,
+    theme: Theme,
+    icons: IconSet,
+}
+
+impl Widget for WarningBanner {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let Some(most_severe) = self.data.iter().map(severity_rank).max() else {
+            return;
+        };
+
+        let background = match most_severe {
+            3.. => self.theme.severity[3],
+            2 => self.theme.severity[2],
+            1 => self.theme.severity[1],
+            _ => self.theme.info,
+        };
+
+        let now = chrono::Utc::now();
+        let mut spans = vec![Span::raw(format!(" {} ", self.icons.warning())).bold()];
+        for (i, warning) in self.data.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" · "));
+            }
+            spans.push(Span::raw(warning.event.clone()).bold());
+            let period = if warning.is_active(now) {
+                warning.expires.map(|expires| ("until", expires))
+            } else {
+                warning.start().map(|start| ("from", start))
+            };
+            if let Some((label, time)) = period {
+                spans.push(Span::raw(format!(
+                    " {} {}",
+                    label,
+                    time.with_timezone(&chrono::Local).format("%a %-I:%M %p")
+                )));
+            }
+        }
+
+        Paragraph::new(Line::from(spans))
+            .style(Style::new().bg(background).fg(self.theme.banner_fg))
+            .render(area, buf);
+    }
+}
+
+impl WarningBanner {
+    pub fn new(warnings: Vec<CapAlert>) -> Self {
+        Self {
+            data: warnings,
+            theme: Theme::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+}
+
+/// CAP severities from least to most severe
+fn severity_rank(warning: &CapAlert) -> u8 {
+    match warning.severity.as_str() {
+        "Extreme" => 4,
+        "Severe" => 3,
+        "Moderate" => 2,
+        "Minor" => 1,
+        _ => 0,
+    }
+}
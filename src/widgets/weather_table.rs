@@ -1,66 +1,285 @@
-use chrono::{Local, NaiveDate, NaiveDateTime, Timelike};
+use chrono::{Local, Timelike};
 use ratatui::{
     layout::Constraint,
-    style::{Color, Style, Stylize},
+    style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, Cell, Row, Table, Widget},
+    widgets::{Block, Cell, Row, StatefulWidget, Table, TableState},
 };
 
-use crate::data::weather::{self, Weather};
+use serde::Deserialize;
+
+use crate::data::{
+    alert_rules::AlertSpan,
+    weather::{self, Weather},
+};
+use crate::{gradient::TemperatureGradient, icons::IconSet, theme::Theme};
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    Time,
+    Condition,
+    Temp,
+    FeelsLike,
+    PrecipProbability,
+    PrecipAmount,
+    Wind,
+    Gusts,
+    Humidity,
+    Uv,
+    CloudCover,
+}
+
+impl Column {
+    pub const ALL: [Column; 11] = [
+        Column::Time,
+        Column::Condition,
+        Column::Temp,
+        Column::FeelsLike,
+        Column::PrecipProbability,
+        Column::PrecipAmount,
+        Column::Wind,
+        Column::Gusts,
+        Column::Humidity,
+        Column::Uv,
+        Column::CloudCover,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Column::Time => "Time",
+            Column::Condition => "Weather",
+            Column::Temp => "Temperature",
+            Column::FeelsLike => "Feels like",
+            Column::PrecipProbability => "Precipitation",
+            Column::PrecipAmount => "Precip",
+            Column::Wind => "Wind",
+            Column::Gusts => "Gusts",
+            Column::Humidity => "Humidity",
+            Column::Uv => "UV",
+            Column::CloudCover => "Clouds",
+        }
+    }
+
+    /// Relative share of the table width when no fixed width is configured
+    pub fn default_width(&self) -> Constraint {
+        match self {
+            Column::Time => Constraint::Fill(4),
+            Column::Condition => Constraint::Fill(7),
+            Column::Temp => Constraint::Fill(5),
+            Column::FeelsLike => Constraint::Fill(5),
+            Column::PrecipProbability => Constraint::Fill(4),
+            Column::PrecipAmount => Constraint::Fill(3),
+            Column::Wind => Constraint::Fill(4),
+            Column::Gusts => Constraint::Fill(3),
+            Column::Humidity => Constraint::Fill(3),
+            Column::Uv => Constraint::Fill(2),
+            Column::CloudCover => Constraint::Fill(3),
+        }
+    }
+
+    /// Cells the column needs to stay readable
+    pub fn min_width(&self) -> u16 {
+        match self {
+            Column::Time => 8,
+            Column::Condition => 14,
+            Column::Temp | Column::FeelsLike => 7,
+            Column::PrecipProbability => 17,
+            Column::PrecipAmount => 7,
+            Column::Wind => 10,
+            Column::Gusts => 6,
+            Column::Humidity | Column::CloudCover => 4,
+            Column::Uv => 2,
+        }
+    }
+}
 
 #[derive(Default)]
 pub struct WeatherTable {
     data: Vec<Weather>,
+    columns: Vec<(Column, Constraint)>,
+    alerts: Vec<AlertSpan>,
+    theme: Theme,
+    gradient: TemperatureGradient,
+    icons: IconSet,
 }
 
-impl Widget for WeatherTable {
-    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
-        if self.data.len() == 0 {
+impl StatefulWidget for WeatherTable {
+    type State = TableState;
+
+    fn render(
+        self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+        state: &mut Self::State,
+    ) {
+        if self.data.is_empty() {
             return;
         }
-        let header = Row::new(vec!["Time", "Weather", "Temperature", "Precipitation"]);
+        // Drop trailing columns that don't fit rather than squeezing them all
+        let mut used = 0;
+        let columns: Vec<_> = self
+            .columns
+            .iter()
+            .enumerate()
+            .take_while(|(i, (column, constraint))| {
+                used += match constraint {
+                    Constraint::Length(width) => *width,
+                    _ => column.min_width(),
+                } + 1;
+                *i == 0 || used <= area.width + 1
+            })
+            .map(|(_, column)| *column)
+            .collect();
+        let header = Row::new(columns.iter().map(|(column, _)| column.label()));
 
         let now = Local::now().naive_local();
         let rows = self.data.iter().map(|i| {
-            let (desc, emoji) = weather::get_weather_description(i.weather_code);
             let time = i.date_time;
             let row_style = if time.date() == now.date() && time.hour() == now.hour() {
-                Style::new().bg(Color::DarkGray).fg(Color::White)
+                Style::new()
+                    .bg(self.theme.current_hour_bg)
+                    .fg(self.theme.current_hour_fg)
             } else {
                 Style::new()
             };
-            Row::new(vec![
-                Cell::from(format!(
-                    "{:>8}",
-                    Self::parse_hour(i.date_time.format("%Y-%m-%dT%H:%M").to_string())
-                        .unwrap_or_default()
-                )),
-                Cell::from(format!("{} {}", emoji, desc)),
-                Cell::from(format!("{:.1}°F", i.temp)),
-                Self::render_precip_bar(i.precip as u8),
-            ])
+            let row_style = if self.alerts.iter().any(|alert| alert.contains(time)) {
+                row_style.fg(self.theme.alert)
+            } else {
+                row_style
+            };
+            Row::new(
+                columns
+                    .iter()
+                    .map(|(column, _)| self.render_cell(*column, i)),
+            )
             .style(row_style)
         });
 
-        let widths = [
-            Constraint::Percentage(20),
-            Constraint::Percentage(35),
-            Constraint::Percentage(25),
-            Constraint::Percentage(20),
-        ];
+        let widths = columns.iter().map(|(_, width)| *width);
 
         let table = Table::new(rows, widths)
             .header(header.style(Style::new().bold()))
             .column_spacing(1)
+            .row_highlight_style(Style::new().fg(self.theme.accent).bold())
             .block(Block::new().title("Hourly"));
 
-        Widget::render(table, area, buf);
+        StatefulWidget::render(table, area, buf, state);
     }
 }
 
 impl WeatherTable {
-    pub fn new(weather: Vec<Weather>) -> Self {
-        Self { data: weather }
+    pub fn new(weather: Vec<Weather>, columns: Vec<(Column, Constraint)>) -> Self {
+        Self {
+            data: weather,
+            columns,
+            alerts: Vec::new(),
+            theme: Theme::default(),
+            gradient: TemperatureGradient::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Color temperature cells by value
+    pub fn gradient(mut self, gradient: TemperatureGradient) -> Self {
+        self.gradient = gradient;
+        self
+    }
+
+    pub fn icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    /// Highlight the hours covered by `alerts`
+    pub fn alerts(mut self, alerts: Vec<AlertSpan>) -> Self {
+        self.alerts = alerts;
+        self
+    }
+
+    fn render_cell(&self, column: Column, weather: &Weather) -> Cell<'static> {
+        let atmosphere = weather.atmosphere.clone().unwrap_or_default();
+        match column {
+            Column::Time => Cell::from(format!(
+                "{:>8}",
+                Self::parse_hour(weather.date_time.format("%Y-%m-%dT%H:%M").to_string())
+                    .unwrap_or_default()
+            )),
+            Column::Condition => Self::cell(
+                weather.weather_code.map(|code| {
+                    format!(
+                        "{} {}",
+                        self.icons.weather(code),
+                        weather::get_weather_description(code)
+                    )
+                }),
+                &self.theme,
+            ),
+            Column::Temp => self.render_temp(weather.temp),
+            Column::FeelsLike => self.render_temp(weather.apparent_temp),
+            Column::PrecipProbability => match weather.precip {
+                Some(precip) => Self::render_precip_bar(precip as u8, &self.theme),
+                None => Self::cell(None, &self.theme),
+            },
+            Column::PrecipAmount => Self::cell(
+                atmosphere
+                    .precip_amount
+                    .map(|amount| format!("{:.1} mm", amount)),
+                &self.theme,
+            ),
+            Column::Wind => Self::cell(
+                atmosphere
+                    .wind_speed
+                    .map(|speed| match atmosphere.wind_direction {
+                        Some(direction) => format!(
+                            "{:.0} mph {}",
+                            speed,
+                            weather::get_cardinal_direction(direction)
+                        ),
+                        None => format!("{:.0} mph", speed),
+                    }),
+                &self.theme,
+            ),
+            Column::Gusts => Self::cell(
+                atmosphere
+                    .wind_gusts
+                    .map(|gusts| format!("{:.0} mph", gusts)),
+                &self.theme,
+            ),
+            Column::Humidity => Self::cell(
+                atmosphere.humidity.map(|humidity| format!("{}%", humidity)),
+                &self.theme,
+            ),
+            Column::Uv => Self::cell(
+                atmosphere.uv_index.map(|uv| format!("{:.0}", uv)),
+                &self.theme,
+            ),
+            Column::CloudCover => Self::cell(
+                atmosphere.cloud_cover.map(|cover| format!("{}%", cover)),
+                &self.theme,
+            ),
+        }
+    }
+
+    /// Values the model didn't provide show as a muted dash
+    fn cell(value: Option<String>, theme: &Theme) -> Cell<'static> {
+        value.map_or(Cell::from("–").fg(theme.muted), Cell::from)
+    }
+
+    fn render_temp(&self, temp: Option<f32>) -> Cell<'static> {
+        let Some(temp) = temp else {
+            return Self::cell(None, &self.theme);
+        };
+        let cell = Cell::from(format!("{:.1}°F", temp));
+        match self.gradient.color(temp) {
+            Some(color) => cell.style(Style::new().fg(color)),
+            None => cell,
+        }
     }
 
     fn parse_hour(time: String) -> Option<String> {
@@ -81,11 +300,11 @@ impl WeatherTable {
         Some(formatted)
     }
 
-    fn render_precip_bar(pct: u8) -> Cell<'static> {
+    fn render_precip_bar(pct: u8, theme: &Theme) -> Cell<'static> {
         let width = 10;
         let filled = (pct as usize * width) / 100;
         let empty_len = width - filled;
-        let filled = Span::styled("█".repeat(filled), Style::new().fg(Color::Blue));
+        let filled = Span::styled("█".repeat(filled), Style::new().fg(theme.precip));
         let empty = Span::raw(" ".repeat(empty_len));
 
         let gauge = Line::from(vec![